@@ -0,0 +1,288 @@
+use std::fmt;
+use std::time::Duration;
+
+use async_std::future::timeout;
+use async_std::task;
+use cynic::{GraphQlResponse, Operation};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+const DEFAULT_ENDPOINT: &str = "https://academic-slc.apps.binus.ac.id/tpa-241/query";
+const ENDPOINT_ENV_VAR: &str = "SR_EXAM_GRAPHQL_ENDPOINT";
+
+#[derive(Debug, Clone)]
+pub enum GraphQlClientError {
+    // Network failures, 5xx responses and timeouts, returned once every retry has been used up.
+    Transport(String),
+    Timeout,
+    // The server answered, but not with a GraphQL response this client understands.
+    Decode(String),
+    // The server answered with a GraphQL `errors` array.
+    GraphQl(Vec<String>),
+    MissingData,
+}
+
+impl GraphQlClientError {
+    // True when the service could not be reached at all, as opposed to answering with an error.
+    pub fn is_offline(&self) -> bool {
+        matches!(self, GraphQlClientError::Transport(_) | GraphQlClientError::Timeout)
+    }
+}
+
+impl fmt::Display for GraphQlClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphQlClientError::Transport(message) => write!(f, "GraphQL request failed: {}", message),
+            GraphQlClientError::Timeout => write!(f, "GraphQL request timed out"),
+            GraphQlClientError::Decode(message) => write!(f, "GraphQL response could not be read: {}", message),
+            GraphQlClientError::GraphQl(messages) => write!(f, "GraphQL errors: {}", messages.join("; ")),
+            GraphQlClientError::MissingData => write!(f, "GraphQL response contained no data"),
+        }
+    }
+}
+
+impl From<GraphQlClientError> for String {
+    fn from(error: GraphQlClientError) -> Self {
+        error.to_string()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GraphQlClient {
+    endpoint: String,
+    timeout: Duration,
+    max_retries: u32,
+    initial_backoff: Duration,
+}
+
+impl GraphQlClient {
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            timeout: Duration::from_secs(15),
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(500),
+        }
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Uses `SR_EXAM_GRAPHQL_ENDPOINT` when set, e.g. to point the app at a local mock server.
+    pub fn from_env() -> Self {
+        let endpoint = std::env::var(ENDPOINT_ENV_VAR).unwrap_or_else(|_| DEFAULT_ENDPOINT.to_string());
+        Self::new(endpoint)
+    }
+
+    /// Runs the operation built by `build`, retrying transport failures and timeouts with
+    /// exponential backoff. GraphQL `errors` and unreadable responses are not retried.
+    pub async fn run<ResponseData, Vars, F>(&self, build: F) -> Result<ResponseData, GraphQlClientError>
+    where
+        F: Fn() -> Operation<ResponseData, Vars>,
+        ResponseData: DeserializeOwned + 'static,
+        Vars: Serialize,
+    {
+        self.post_with_retries(build).await
+    }
+
+    async fn post_with_retries<ResponseData, Body, F>(&self, build: F) -> Result<ResponseData, GraphQlClientError>
+    where
+        F: Fn() -> Body,
+        Body: Serialize,
+        ResponseData: DeserializeOwned,
+    {
+        let mut attempt = 0;
+        loop {
+            let failure = match timeout(self.timeout, self.post(&build())).await {
+                Ok(Ok(body)) => return decode_response(&body),
+                Ok(Err(failure)) => failure,
+                Err(_) => GraphQlClientError::Timeout,
+            };
+
+            if !failure.is_offline() || attempt >= self.max_retries {
+                return Err(failure);
+            }
+
+            task::sleep(self.initial_backoff * 2u32.pow(attempt)).await;
+            attempt += 1;
+        }
+    }
+
+    async fn post(&self, body: &impl Serialize) -> Result<Vec<u8>, GraphQlClientError> {
+        let request = surf::post(self.endpoint.as_str())
+            .body_json(body)
+            .map_err(|e| GraphQlClientError::Decode(e.to_string()))?;
+        let mut response = request.await.map_err(|e| GraphQlClientError::Transport(e.to_string()))?;
+
+        if response.status().is_server_error() {
+            return Err(GraphQlClientError::Transport(format!("server responded with {}", response.status())));
+        }
+        response.body_bytes().await.map_err(|e| GraphQlClientError::Transport(e.to_string()))
+    }
+}
+
+fn decode_response<ResponseData: DeserializeOwned>(body: &[u8]) -> Result<ResponseData, GraphQlClientError> {
+    let response: GraphQlResponse<ResponseData> =
+        serde_json::from_slice(body).map_err(|e| GraphQlClientError::Decode(e.to_string()))?;
+
+    if let Some(errors) = response.errors.filter(|errors| !errors.is_empty()) {
+        return Err(GraphQlClientError::GraphQl(errors.into_iter().map(|error| error.message).collect()));
+    }
+    response.data.ok_or(GraphQlClientError::MissingData)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+
+    enum MockResponse {
+        Status(u16, &'static str),
+        Hang(Duration),
+    }
+
+    // Serves one canned response per connection, repeating the last one, and counts the requests.
+    fn mock_server(responses: Vec<MockResponse>) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}/query", listener.local_addr().unwrap());
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&requests);
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { break };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut content_length = 0;
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap_or(0) > 0 && line != "\r\n" {
+                    if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                        content_length = value.trim().parse().unwrap_or(0);
+                    }
+                    line.clear();
+                }
+                let mut body = vec![0; content_length];
+                let _ = reader.read_exact(&mut body);
+
+                let index = counter.fetch_add(1, Ordering::SeqCst);
+                match &responses[index.min(responses.len() - 1)] {
+                    MockResponse::Status(status, body) => {
+                        let _ = write!(
+                            stream,
+                            "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                            status,
+                            body.len(),
+                            body
+                        );
+                    }
+                    MockResponse::Hang(duration) => thread::sleep(*duration),
+                }
+            }
+        });
+
+        (endpoint, requests)
+    }
+
+    fn client(endpoint: String) -> GraphQlClient {
+        GraphQlClient {
+            endpoint,
+            timeout: Duration::from_secs(5),
+            max_retries: 2,
+            initial_backoff: Duration::from_millis(10),
+        }
+    }
+
+    fn query(client: &GraphQlClient) -> Result<serde_json::Value, GraphQlClientError> {
+        task::block_on(client.post_with_retries(|| serde_json::json!({ "query": "{ ping }" })))
+    }
+
+    #[test]
+    fn from_env_reads_the_endpoint_override() {
+        std::env::set_var(ENDPOINT_ENV_VAR, "http://127.0.0.1:4000/query");
+        assert_eq!(GraphQlClient::from_env().endpoint, "http://127.0.0.1:4000/query");
+        std::env::remove_var(ENDPOINT_ENV_VAR);
+        assert_eq!(GraphQlClient::from_env().endpoint, DEFAULT_ENDPOINT);
+    }
+
+    #[test]
+    fn returns_data_of_a_successful_response() {
+        let (endpoint, requests) = mock_server(vec![MockResponse::Status(200, r#"{"data":{"ping":"pong"}}"#)]);
+
+        let data = query(&client(endpoint)).unwrap();
+        assert_eq!(data["ping"], "pong");
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn retries_server_errors_until_success() {
+        let (endpoint, requests) = mock_server(vec![
+            MockResponse::Status(503, "{}"),
+            MockResponse::Status(200, r#"{"data":{"ping":"pong"}}"#),
+        ]);
+
+        assert!(query(&client(endpoint)).is_ok());
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn gives_up_after_max_retries_as_offline() {
+        let (endpoint, requests) = mock_server(vec![MockResponse::Status(503, "{}")]);
+
+        let error = query(&client(endpoint)).unwrap_err();
+        assert!(matches!(error, GraphQlClientError::Transport(_)), "{}", error);
+        assert!(error.is_offline());
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn does_not_retry_graphql_errors() {
+        let (endpoint, requests) = mock_server(vec![MockResponse::Status(200, r#"{"data":null,"errors":[{"message":"unknown nim"}]}"#)]);
+
+        let error = query(&client(endpoint)).unwrap_err();
+        assert!(matches!(&error, GraphQlClientError::GraphQl(messages) if messages == &["unknown nim"]), "{}", error);
+        assert!(!error.is_offline());
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn does_not_retry_unreadable_responses() {
+        let (endpoint, requests) = mock_server(vec![MockResponse::Status(200, "<html>maintenance</html>")]);
+
+        let error = query(&client(endpoint)).unwrap_err();
+        assert!(matches!(error, GraphQlClientError::Decode(_)), "{}", error);
+        assert!(!error.is_offline());
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn times_out_a_silent_server() {
+        let (endpoint, _) = mock_server(vec![MockResponse::Hang(Duration::from_secs(2))]);
+        let client = client(endpoint).with_timeout(Duration::from_millis(200)).with_max_retries(0);
+
+        let error = query(&client).unwrap_err();
+        assert!(matches!(error, GraphQlClientError::Timeout), "{}", error);
+        assert!(error.is_offline());
+    }
+
+    #[test]
+    fn reports_an_unreachable_endpoint_as_offline() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}/query", listener.local_addr().unwrap());
+        drop(listener);
+
+        let error = query(&client(endpoint).with_max_retries(0)).unwrap_err();
+        assert!(matches!(error, GraphQlClientError::Transport(_)), "{}", error);
+        assert!(error.is_offline());
+    }
+}
@@ -1,22 +1,35 @@
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
 use bcrypt::{hash, verify, DEFAULT_COST};
-use cynic::http::SurfExt;
 use cynic::{QueryBuilder};
 use schema::__fields;
 use schema::__fields::Enrollment::class_code;
-use schema::__fields::Query::_get_room_by_number_arguments::room_number as room_number_arg;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Mutex;
+use std::time::Duration;
 use mysql::{prelude::*, TxOpts};
 use mysql::{PooledConn, params};
 use mysql::Pool;
-use tauri::{State};
-use async_std::task;
+use tauri::{AppHandle, Manager, State};
+use tauri::api::dialog::blocking::FileDialogBuilder;
+use rust_xlsxwriter::{Format, Workbook, XlsxError};
+use calamine::{open_workbook_auto, DataType, Reader};
+use chrono::Datelike;
+use printpdf::{BuiltinFont, IndirectFontRef, Line, Mm, PdfDocument, PdfLayerReference, Point};
 use rand::Rng;
+use rand::rngs::OsRng;
+
+mod graphql_client;
+
+use graphql_client::{GraphQlClient, GraphQlClientError};
 
 #[cynic::schema("sr-exam")]
 mod schema {}
 
-#[derive(cynic::QueryFragment, Debug, Serialize, Clone)]
+#[derive(cynic::QueryFragment, Debug, Serialize, Deserialize, Clone)]
 #[cynic(graphql_type = "User")]
 pub struct User {
     #[cynic(rename = "bn_number")]
@@ -40,9 +53,10 @@ pub struct GetAllSubject {
     pub get_all_subject: Vec<Subject>,
 }
 
-#[derive(cynic::QueryFragment, Debug, Serialize, Clone)]
+#[derive(cynic::QueryFragment, Debug, Serialize, Deserialize, Clone)]
 pub struct Subject {
     #[cynic(rename = "subject_code")]
+    #[serde(alias = "subject_code")]
     pub subject_code_str: String,
     #[cynic(rename = "subject_name")]
     pub subject_name: String,
@@ -54,12 +68,13 @@ pub struct GetAllRoom {
     pub get_all_room: Vec<Room>,
 }
 
-#[derive(cynic::QueryFragment, Debug, Serialize)]
+#[derive(cynic::QueryFragment, Debug, Serialize, Deserialize, Clone)]
 pub struct Room {
     pub campus: String,
     #[cynic(rename = "room_capacity")]
     pub room_capacity: i32,
     #[cynic(rename = "room_number")]
+    #[serde(alias = "room_number")]
     pub room_number_str: String,
 }
 
@@ -69,9 +84,10 @@ pub struct GetAllEnrollment {
     pub get_all_enrollment: Option<Vec<Option<Enrollment>>>,
 }
 
-#[derive(cynic::QueryFragment, Debug, Serialize, Clone)]
+#[derive(cynic::QueryFragment, Debug, Serialize, Deserialize, Clone)]
 pub struct Enrollment {
     #[cynic(rename = "class_code")]
+    #[serde(alias = "class_code")]
     pub class_code_str: String,
     pub nim: String,
     #[cynic(rename = "subject_code")]
@@ -83,16 +99,138 @@ pub struct Shift {
     pub shift_id: String,
     pub start_time: String, 
     pub end_time: String,   
+    pub retired: bool,
+}
+
+#[derive(cynic::QueryVariables, Debug)]
+pub struct NimArguments {
+    pub nim: String,
 }
 
 #[derive(cynic::QueryFragment, Debug)]
-#[cynic(graphql_type = "Query")]
+#[cynic(graphql_type = "Query", variables = "NimArguments")]
 pub struct PasswordbyNIM {
-    #[arguments(nim = "&str")]
+    #[arguments(nim: $nim)]
     #[cynic(rename = "getPasswordByNIM")]
     pub get_password_by_nim: String,
 }
 
+#[derive(cynic::QueryVariables, Debug)]
+pub struct InitialArguments {
+    pub initial: String,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Query", variables = "InitialArguments")]
+pub struct PasswordByInitial {
+    #[arguments(initial: $initial)]
+    #[cynic(rename = "getPasswordByInitial")]
+    pub get_password_by_initial: String,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Query", variables = "NimArguments")]
+pub struct UserByNIM {
+    #[arguments(nim: $nim)]
+    #[cynic(rename = "getUserByNIM")]
+    pub get_user_by_nim: User,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Query", variables = "InitialArguments")]
+pub struct UserByInitial {
+    #[arguments(initial: $initial)]
+    pub get_user_by_initial: User,
+}
+
+#[derive(cynic::QueryVariables, Debug)]
+pub struct SubjectCodeArguments {
+    pub subject_code: String,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Query", variables = "SubjectCodeArguments")]
+pub struct EnrollmentClassCodeBySubjectCode {
+    #[arguments(subject_code: $subject_code)]
+    pub get_enrollment_class_code_by_subject_code: Option<Vec<String>>,
+}
+
+#[derive(cynic::QueryVariables, Debug)]
+pub struct ClassAndSubjectArguments {
+    pub class_code: String,
+    pub subject_code: String,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Query", variables = "ClassAndSubjectArguments")]
+pub struct StudentsByClassAndSubjectCode {
+    #[arguments(class_code: $class_code, subject_code: $subject_code)]
+    pub get_students_by_class_and_subject_code: Option<Vec<String>>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Query", variables = "NimArguments")]
+pub struct EnrollmentByNIM {
+    #[arguments(nim: $nim)]
+    #[cynic(rename = "getEnrollmentByNIM")]
+    pub get_enrollment_by_nim: Option<Vec<Enrollment>>,
+}
+
+#[derive(cynic::QueryVariables, Debug)]
+pub struct RoomNumberArguments {
+    pub room_number: String,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Query", variables = "RoomNumberArguments")]
+pub struct RoomByNumber {
+    #[arguments(room_number: $room_number)]
+    pub get_room_by_number: Room,
+}
+
+#[derive(cynic::QueryVariables, Debug)]
+pub struct CampusArguments {
+    pub campus: String,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Query", variables = "CampusArguments")]
+pub struct RoomByCampus {
+    #[arguments(campus: $campus)]
+    pub get_room_by_campus: Vec<Room>,
+}
+
+#[derive(cynic::QueryVariables, Debug)]
+pub struct RoomCapacityArguments {
+    pub room_capacity: i32,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Query", variables = "RoomCapacityArguments")]
+pub struct RoomByCapacity {
+    #[arguments(room_capacity: $room_capacity)]
+    pub get_room_by_capacity: Vec<Room>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Query", variables = "SubjectCodeArguments")]
+pub struct SubjectByCode {
+    #[arguments(subject_code: $subject_code)]
+    pub get_subject_by_code: Subject,
+}
+
+#[derive(cynic::QueryVariables, Debug)]
+pub struct SubjectNameArguments {
+    pub subject_name: String,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Query", variables = "SubjectNameArguments")]
+pub struct SubjectByName {
+    #[arguments(subject_name: $subject_name)]
+    pub get_subject_by_name: Vec<Subject>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 struct RoomTransaction {
   room_number: String, 
@@ -108,342 +246,6134 @@ pub struct ExamTransaction {
     pub transaction_date: String,
     pub proctor: Option<String>,
     pub status: Option<String>,
+    pub period_id: Option<u32>,
+    // Original exam when this is a makeup exam.
+    pub makeup_of: Option<String>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-struct CurrentUser {
-    user: User,
+#[derive(Debug, Serialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: u64,
+    pub next_cursor: Option<String>,
 }
 
-struct AppState {
-    user: Mutex<Option<CurrentUser>>,
-    mysql_pool: Pool,
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+pub struct PageRequest {
+    pub sort_by: Option<String>,
+    pub descending: bool,
+    pub cursor: Option<String>,
+    pub limit: Option<u32>,
 }
 
-struct MySQLConfig {
-    user: String,
-    password: String,
-    host: String,
-    database: String,
+#[derive(Debug, Serialize, Deserialize)]
+struct PageCursor {
+    values: Vec<String>,
+    key: String,
 }
 
-impl MySQLConfig {
-    fn new(user: String, password: String, host: String, database: String) -> Self {
-        Self {
-            user,
-            password,
-            host,
-            database,
-        }
-    }
-
-    fn format_url(&self) -> String {
-        format!(
-            "mysql://{}:{}@{}/{}",
-            self.user, self.password, self.host, self.database
-        )
-    }
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+pub struct UserFilter {
+    pub role: Option<String>,
+    pub major: Option<String>,
 }
 
-#[tauri::command]
-fn login(
-    name: String,
-    password: String,
-    mysql_pool: State<'_, Pool>,
-    current_user: State<'_, AppState>,
-) -> Result<Option<String>, String> {
-    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
-    let is_nim = name.chars().all(char::is_numeric);
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+pub struct RoomFilter {
+    pub campus: Option<String>,
+    pub min_capacity: Option<i32>,
+}
 
-    let query = "SELECT bn_number, name, major, initial, nim, role, password FROM users WHERE nim = :name OR initial = :name";
-    let params = params! { "name" => name.clone() };
-    let result: Option<(String, String, String, Option<String>, String, String, Option<String>)> = conn.exec_first(query, params).map_err(|e| format!("Failed to execute query: {}", e))?;
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+pub struct SubjectFilter {
+    pub name: Option<String>,
+}
 
-    if let Some((bn_number, name, major, initial, nim, role, stored_password)) = result {
-        if stored_password.is_none() || verify(&password, &stored_password.unwrap_or_default()).map_err(|e| format!("Failed to verify password: {}", e))? {
-            let user = User {
-                bn_number: bn_number.into(),
-                nim: nim.clone(),
-                name,
-                major,
-                role,
-                initial,
-            };
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+pub struct ExamTransactionFilter {
+    pub date_from: Option<String>,
+    pub date_to: Option<String>,
+    pub status: Option<String>,
+    pub proctor: Option<String>,
+    pub subject_code: Option<String>,
+    pub room_number: Option<String>,
+    pub campus: Option<String>,
+    // Without a period, exams of archived periods are left out.
+    pub period_id: Option<u32>,
+}
 
-            match current_user.user.lock() {
-                Ok(mut user_lock) => *user_lock = Some(CurrentUser { user }),
-                Err(e) => return Err(format!("Failed to lock mutex: {}", e)),
-            };
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SearchHit {
+    User {
+        bn_number: String,
+        nim: Option<String>,
+        name: String,
+        initial: Option<String>,
+        role: Option<String>,
+        score: u32,
+    },
+    Subject {
+        subject_code: String,
+        subject_name: String,
+        score: u32,
+    },
+    Room {
+        room_number: String,
+        campus: String,
+        room_capacity: i32,
+        score: u32,
+    },
+    ExamTransaction {
+        transaction_id: String,
+        subject_code: String,
+        subject_name: String,
+        room_number: String,
+        transaction_date: String,
+        shift_id: String,
+        enrolled_nim: Option<String>,
+        score: u32,
+    },
+}
 
-            return Ok(Some(if is_nim { "nim" } else { "initial" }.to_string()));
-        } else {
-            return Ok(None);
+impl SearchHit {
+    fn score(&self) -> u32 {
+        match self {
+            SearchHit::User { score, .. }
+            | SearchHit::Subject { score, .. }
+            | SearchHit::Room { score, .. }
+            | SearchHit::ExamTransaction { score, .. } => *score,
         }
     }
-    Ok(None)
 }
 
-#[tauri::command]
-fn change_password(
-    old_password: String,
-    new_password: String,
-    mysql_pool: State<'_, Pool>,
-    current_user: State<'_, AppState>,
-) -> Result<bool, String> {
-    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
-
-    let user_guard = current_user.user.lock().map_err(|e| format!("Failed to lock mutex: {}", e))?;
-
-    if let Some(current_user) = &*user_guard {
-        let nim = &current_user.user.nim;
-        let initial = &current_user.user.initial;
+#[derive(Debug, Serialize)]
+pub struct StudentExam {
+    pub transaction_id: String,
+    pub subject_code: String,
+    pub subject_name: String,
+    pub transaction_date: String,
+    pub start_time: String,
+    pub end_time: String,
+    pub room_number: String,
+    pub campus: String,
+    pub seat_number: Option<u32>,
+    pub status: Option<String>,
+}
 
-        let query = if let Some(initial) = initial {
-            "SELECT password FROM users WHERE nim = :nim OR initial = :initial"
-        } else {
-            "SELECT password FROM users WHERE nim = :nim"
-        };
+#[derive(Debug, Serialize)]
+pub struct StudentSchedule {
+    pub upcoming: Vec<StudentExam>,
+    pub past: Vec<StudentExam>,
+}
 
-        let params = if let Some(initial) = initial {
-            params! { "nim" => nim, "initial" => initial }
-        } else {
-            params! { "nim" => nim }
-        };
+#[derive(Debug, Serialize)]
+pub struct SeatAllocation {
+    pub seated: u32,
+    pub unseated: Vec<String>,
+}
 
-        let stored_password: Option<String> = conn.exec_first(query, params).map_err(|e| format!("Failed to execute query: {}", e))?;
+#[derive(Debug, Serialize)]
+pub struct ProctorDuty {
+    pub transaction_id: String,
+    pub subject_code: String,
+    pub subject_name: String,
+    pub transaction_date: String,
+    pub start_time: String,
+    pub end_time: String,
+    pub room_number: String,
+    pub campus: String,
+    pub status: Option<String>,
+    pub hours: f64,
+}
 
-        if let Some(stored_password) = stored_password {
-            if stored_password.is_empty() || verify(&old_password, &stored_password).map_err(|e| format!("Failed to verify password: {}", e))? {
-                if !new_password.is_empty() {
-                    let hashed_new_password = hash(new_password, DEFAULT_COST).map_err(|e| format!("Failed to hash password: {}", e))?;
+#[derive(Debug, Serialize)]
+pub struct ProctorWorkload {
+    pub period: String,
+    pub duties: u32,
+    pub hours: f64,
+}
 
-                    let update_query = if let Some(initial) = initial {
-                        "UPDATE users SET password = :new_password WHERE nim = :nim OR initial = :initial"
-                    } else {
-                        "UPDATE users SET password = :new_password WHERE nim = :nim"
-                    };
+#[derive(Debug, Serialize)]
+pub struct DutyRoster {
+    pub duties: Vec<ProctorDuty>,
+    pub workload: Vec<ProctorWorkload>,
+    pub total_hours: f64,
+}
 
-                    let update_params = if let Some(initial) = initial {
-                        params! { "nim" => nim, "initial" => initial, "new_password" => hashed_new_password }
-                    } else {
-                        params! { "nim" => nim, "new_password" => hashed_new_password }
-                    };
+#[derive(Debug, Serialize, Default)]
+pub struct Utilization {
+    pub booked_shifts: u32,
+    pub available_shifts: u32,
+    pub shift_utilization: f64,
+    pub seats_used: u32,
+    // Effective capacity summed over the booked shifts
+    pub seats_offered: u32,
+    pub seat_utilization: f64,
+}
 
-                    conn.exec_drop(update_query, update_params).map_err(|e| format!("Failed to update password: {}", e))?;
+impl Utilization {
+    fn add(&mut self, other: &Utilization) {
+        self.booked_shifts += other.booked_shifts;
+        self.available_shifts += other.available_shifts;
+        self.seats_used += other.seats_used;
+        self.seats_offered += other.seats_offered;
+    }
 
-                    return Ok(true);
-                } else {
-                    return Err("New password cannot be empty".to_string());
-                }
-            } else {
-                return Ok(false);
-            }
-        } else {
-            return Err("Stored password is NULL".to_string());
-        }
+    fn finish(&mut self) {
+        let ratio = |used: u32, total: u32| if total == 0 { 0.0 } else { used as f64 / total as f64 };
+        self.shift_utilization = ratio(self.booked_shifts, self.available_shifts);
+        self.seat_utilization = ratio(self.seats_used, self.seats_offered);
     }
-    Err("Current user not authenticated".to_string())
 }
 
-#[tauri::command]
-fn edit_role(
-    bn_number: String,
-    new_role: String,
-    mysql_pool: State<'_, Pool>,
-) -> Result<(), String> {
-    let mut conn: PooledConn = mysql_pool
-        .get_conn()
-        .map_err(|e| format!("Failed to get connection: {}", e))?;
+#[derive(Debug, Serialize)]
+pub struct RoomUtilization {
+    pub room_number: String,
+    pub campus: String,
+    pub room_capacity: i32,
+    #[serde(flatten)]
+    pub utilization: Utilization,
+}
 
-    conn.exec_drop(
-        "UPDATE users SET role = :role WHERE bn_number = :bn_number",
-        params! {
-            "role" => new_role,
-            "bn_number" => bn_number,
-        },
-    )
-    .map_err(|e| format!("Failed to update user role: {}", e))?;
+#[derive(Debug, Serialize)]
+pub struct CampusUtilization {
+    pub campus: String,
+    pub rooms: u32,
+    #[serde(flatten)]
+    pub utilization: Utilization,
+}
 
-    Ok(())
+#[derive(Debug, Serialize)]
+pub struct DayLoad {
+    pub date: String,
+    pub booked_shifts: u32,
+    pub available_shifts: u32,
 }
 
-#[tauri::command]
-async fn update_exam_transaction(transaction_id: String, proctor: String, status: String, mysql_pool: State<'_, Pool>) -> Result<(), String> {
-  let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+#[derive(Debug, Serialize)]
+pub struct UtilizationReport {
+    pub date_from: String,
+    pub date_to: String,
+    pub rooms: Vec<RoomUtilization>,
+    pub campuses: Vec<CampusUtilization>,
+    pub total: Utilization,
+    // Busiest days first.
+    pub peak_days: Vec<DayLoad>,
+    pub idle_rooms: Vec<String>,
+}
 
-  conn.exec_drop(
-    "UPDATE exam_transaction SET proctor = :proctor, status = :status WHERE transaction_id = :transaction_id",
-    params! {
-      "transaction_id" => transaction_id,
-      "proctor" => proctor,
-      "status" => status,
-    },
-  ).map_err(|e| format!("Failed to update exam transaction: {}", e))?;
+#[derive(Debug, Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum RosterPeriod {
+    Week,
+    #[default]
+    Month,
+}
 
-  Ok(())
+#[derive(Debug, Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    Csv,
+    Xlsx,
 }
 
-#[tauri::command]
-fn get_current_user(state: State<'_, AppState>) -> Option<CurrentUser> {
-    state.user.lock().unwrap().clone()
+#[derive(Debug, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportColumn {
+    TransactionId,
+    SubjectCode,
+    SubjectName,
+    TransactionDate,
+    ShiftId,
+    StartTime,
+    EndTime,
+    RoomNumber,
+    Campus,
+    Proctor,
+    ProctorName,
+    Status,
+    EnrolledCount,
+    SeatedCount,
 }
 
-#[tauri::command]
-async fn get_all_users() -> Result<Vec<User>, ()> {
-    let operation = UsersQuery::build(());
-    let response = surf::post("https://academic-slc.apps.binus.ac.id/tpa-241/query")
-        .run_graphql(operation)
-        .await;
+impl ReportColumn {
+    const ALL: [ReportColumn; 14] = [
+        ReportColumn::TransactionId,
+        ReportColumn::SubjectCode,
+        ReportColumn::SubjectName,
+        ReportColumn::TransactionDate,
+        ReportColumn::ShiftId,
+        ReportColumn::StartTime,
+        ReportColumn::EndTime,
+        ReportColumn::RoomNumber,
+        ReportColumn::Campus,
+        ReportColumn::Proctor,
+        ReportColumn::ProctorName,
+        ReportColumn::Status,
+        ReportColumn::EnrolledCount,
+        ReportColumn::SeatedCount,
+    ];
 
-    match response {
-        Ok(response) => {
-            let data = response.data.unwrap();
-            Ok(data.get_all_user)
+    fn header(&self) -> &'static str {
+        match self {
+            ReportColumn::TransactionId => "Transaction ID",
+            ReportColumn::SubjectCode => "Subject Code",
+            ReportColumn::SubjectName => "Subject Name",
+            ReportColumn::TransactionDate => "Date",
+            ReportColumn::ShiftId => "Shift",
+            ReportColumn::StartTime => "Start",
+            ReportColumn::EndTime => "End",
+            ReportColumn::RoomNumber => "Room",
+            ReportColumn::Campus => "Campus",
+            ReportColumn::Proctor => "Proctor",
+            ReportColumn::ProctorName => "Proctor Name",
+            ReportColumn::Status => "Status",
+            ReportColumn::EnrolledCount => "Enrolled",
+            ReportColumn::SeatedCount => "Seated",
         }
-        Err(_) => Err(()),
     }
 }
 
-#[tauri::command]
-async fn get_all_subject() -> Result<Vec<Subject>, ()>{
-    let operation = GetAllSubject::build(());
-    let response = surf::post("https://academic-slc.apps.binus.ac.id/tpa-241/query")
-        .run_graphql(operation)
-        .await;
-
-    match response{
-        Ok(response) => {
-            let data = response.data.unwrap();
-            Ok(data.get_all_subject)
-        }
-        Err(_) => Err(()),
-    } 
+struct ExamReportRow {
+    transaction_id: String,
+    subject_code: String,
+    subject_name: String,
+    transaction_date: String,
+    shift_id: String,
+    start_time: String,
+    end_time: String,
+    room_number: String,
+    campus: String,
+    proctor: Option<String>,
+    proctor_name: Option<String>,
+    status: Option<String>,
+    enrolled_count: u32,
+    seated_count: u32,
 }
 
-#[tauri::command]
-async fn get_all_room() -> Result<Vec<Room>, String> {
-    let operation = GetAllRoom::build(());
-    let response = surf::post("https://academic-slc.apps.binus.ac.id/tpa-241/query")
-        .run_graphql(operation)
-        .await;
+enum ReportCell {
+    Text(String),
+    Number(f64),
+}
 
-    match response {
-        Ok(response) => {
-            let data = response.data.unwrap();
-            Ok(data.get_all_room)
+impl ExamReportRow {
+    fn cell(&self, column: ReportColumn) -> ReportCell {
+        let text = |value: &str| ReportCell::Text(value.to_string());
+        match column {
+            ReportColumn::TransactionId => text(&self.transaction_id),
+            ReportColumn::SubjectCode => text(&self.subject_code),
+            ReportColumn::SubjectName => text(&self.subject_name),
+            ReportColumn::TransactionDate => text(&self.transaction_date),
+            ReportColumn::ShiftId => text(&self.shift_id),
+            ReportColumn::StartTime => text(&self.start_time),
+            ReportColumn::EndTime => text(&self.end_time),
+            ReportColumn::RoomNumber => text(&self.room_number),
+            ReportColumn::Campus => text(&self.campus),
+            ReportColumn::Proctor => text(self.proctor.as_deref().unwrap_or_default()),
+            ReportColumn::ProctorName => text(self.proctor_name.as_deref().unwrap_or_default()),
+            ReportColumn::Status => text(self.status.as_deref().unwrap_or_default()),
+            ReportColumn::EnrolledCount => ReportCell::Number(self.enrolled_count.into()),
+            ReportColumn::SeatedCount => ReportCell::Number(self.seated_count.into()),
         }
-        Err(_) => Err("Failed to fetch rooms".to_string()),
     }
 }
 
-#[tauri::command]
-async fn get_all_enrollment() -> Result<Vec<Enrollment>, String> {
-    let operation = GetAllEnrollment::build(());
-    let response = surf::post("https://academic-slc.apps.binus.ac.id/tpa-241/query")
-        .run_graphql(operation)
-        .await;
-
-    match response {
-        Ok(response) => {
-            let data = response.data.unwrap();
-            if let Some(enrollments) = data.get_all_enrollment {
-                let enrollments: Vec<Enrollment> = enrollments
-                    .into_iter()
-                    .filter_map(|opt_enrollment| opt_enrollment)
-                    .collect();
-                Ok(enrollments)
-            } else {
-                Err("No enrollments found".to_string())
-            }
-        }
-        Err(_) => Err("Failed to fetch enrollment".to_string()),
-    }
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct CurrentUser {
+    user: User,
+    roles: Vec<String>,
 }
 
-#[tauri::command]
-async fn get_all_shifts(mysql_pool: State<'_, Pool>) -> Result<Vec<Shift>, String> {
-    let mut conn: PooledConn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
-    let shifts_query = "SELECT shift_id, start_time, end_time FROM shift"; 
-    let shifts: Vec<Shift> = conn.query_map(shifts_query, |(shift_id, start_time, end_time)| {
-        Shift {
-            shift_id,
-            start_time,
-            end_time,
-        }
-    }).map_err(|e| format!("Failed to fetch shifts: {}", e))?;
+#[derive(Debug, Serialize, Clone)]
+pub struct RoleGrant {
+    pub bn_number: String,
+    pub role_name: String,
+    pub valid_from: Option<String>,
+    pub valid_until: Option<String>,
+    pub granted_by: String,
+}
 
-    Ok(shifts)
+#[derive(Debug, Serialize, Clone)]
+pub struct RoleChange {
+    pub bn_number: String,
+    pub role_name: String,
+    pub action: String,
+    pub valid_from: Option<String>,
+    pub valid_until: Option<String>,
+    pub changed_by: String,
+    pub changed_at: String,
+}
+
+struct AppState {
+    user: Mutex<Option<CurrentUser>>,
+    mysql_pool: Pool,
+    authenticators: Vec<Box<dyn Authenticator>>,
+}
+
+struct LoginIdentity {
+    nim: String,
+    initial: Option<String>,
+    is_nim: bool,
+    stored_password: Option<String>,
+}
+
+enum AuthOutcome {
+    Accepted,
+    Rejected,
+    // The source could not answer (offline, unknown user); the next authenticator is tried.
+    Unavailable,
+}
+
+type AuthFuture<'a> = Pin<Box<dyn Future<Output = Result<AuthOutcome, String>> + Send + 'a>>;
+
+trait Authenticator: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn authenticate<'a>(&'a self, identity: &'a LoginIdentity, password: &'a str) -> AuthFuture<'a>;
+}
+
+// Fallback cache of the last password the remote accepted, used only while the remote cannot answer.
+struct LocalAuthenticator;
+
+impl Authenticator for LocalAuthenticator {
+    fn name(&self) -> &'static str {
+        "local"
+    }
+
+    fn authenticate<'a>(&'a self, identity: &'a LoginIdentity, password: &'a str) -> AuthFuture<'a> {
+        let outcome = match identity.stored_password.as_deref() {
+            None | Some("") => Ok(AuthOutcome::Unavailable),
+            Some(stored_hash) => verify_password(password, stored_hash).map(|matches| {
+                if matches { AuthOutcome::Accepted } else { AuthOutcome::Rejected }
+            }),
+        };
+        Box::pin(std::future::ready(outcome))
+    }
+}
+
+struct RemoteAuthenticator {
+    client: GraphQlClient,
+}
+
+impl RemoteAuthenticator {
+    async fn fetch_password(&self, identity: &LoginIdentity) -> Option<String> {
+        if identity.is_nim {
+            self.client.run(|| PasswordbyNIM::build(NimArguments { nim: identity.nim.clone() }))
+                .await
+                .ok()
+                .map(|data| data.get_password_by_nim)
+        } else {
+            let initial = identity.initial.clone()?;
+            self.client.run(|| PasswordByInitial::build(InitialArguments { initial: initial.clone() }))
+                .await
+                .ok()
+                .map(|data| data.get_password_by_initial)
+        }
+    }
+}
+
+impl Authenticator for RemoteAuthenticator {
+    fn name(&self) -> &'static str {
+        "remote"
+    }
+
+    fn authenticate<'a>(&'a self, identity: &'a LoginIdentity, password: &'a str) -> AuthFuture<'a> {
+        Box::pin(async move {
+            let remote_password = match self.fetch_password(identity).await {
+                Some(remote_password) if !remote_password.is_empty() => remote_password,
+                _ => return Ok(AuthOutcome::Unavailable),
+            };
+
+            // The credential source may hand out either a hash or the plain password.
+            let matches = if PasswordScheme::detect(&remote_password).is_some() {
+                verify_password(password, &remote_password)?
+            } else {
+                constant_time_eq(password.as_bytes(), remote_password.as_bytes())
+            };
+
+            Ok(if matches { AuthOutcome::Accepted } else { AuthOutcome::Rejected })
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Dataset {
+    Users,
+    Rooms,
+    Subjects,
+    Enrollments,
+}
+
+impl Dataset {
+    // Ordered so that enrollment rows find their users and subjects already present.
+    const ALL: [Dataset; 4] = [Dataset::Users, Dataset::Rooms, Dataset::Subjects, Dataset::Enrollments];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Dataset::Users => "users",
+            Dataset::Rooms => "room",
+            Dataset::Subjects => "subject",
+            Dataset::Enrollments => "enrollment",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Cached<T> {
+    pub data: Vec<T>,
+    pub last_synced_at: Option<String>,
+    pub stale: bool,
+    pub refreshing: bool,
+}
+
+struct SyncCache {
+    ttl_minutes: Mutex<u64>,
+    refreshing: Mutex<HashSet<Dataset>>,
+}
+
+impl SyncCache {
+    fn from_env() -> Self {
+        let ttl_minutes = std::env::var("SR_EXAM_CACHE_TTL_MINUTES")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(60);
+
+        Self {
+            ttl_minutes: Mutex::new(ttl_minutes),
+            refreshing: Mutex::new(HashSet::new()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct RoleDifference {
+    pub bn_number: String,
+    pub nim: String,
+    pub name: String,
+    pub initial: Option<String>,
+    pub local_role: String,
+    pub remote_role: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RoleSource {
+    Local,
+    Remote,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PasswordPolicy {
+    pub min_length: u32,
+    pub require_uppercase: bool,
+    pub require_lowercase: bool,
+    pub require_digit: bool,
+    pub require_symbol: bool,
+    pub history_size: u32,
+    pub forbid_personal_info: bool,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            min_length: 8,
+            require_uppercase: true,
+            require_lowercase: true,
+            require_digit: true,
+            require_symbol: false,
+            history_size: 5,
+            forbid_personal_info: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PasswordScheme {
+    Bcrypt,
+    Argon2id,
+}
+
+impl PasswordScheme {
+    // New hashes are always written with this scheme; anything else is rehashed on login.
+    const CURRENT: PasswordScheme = PasswordScheme::Argon2id;
+
+    fn detect(stored_hash: &str) -> Option<Self> {
+        if stored_hash.starts_with("$argon2id$") {
+            Some(PasswordScheme::Argon2id)
+        } else if stored_hash.starts_with("$2") {
+            Some(PasswordScheme::Bcrypt)
+        } else {
+            None
+        }
+    }
+
+    fn hash(&self, password: &str) -> Result<String, String> {
+        match self {
+            PasswordScheme::Bcrypt => hash(password, DEFAULT_COST).map_err(|e| format!("Failed to hash password: {}", e)),
+            PasswordScheme::Argon2id => {
+                let salt = SaltString::generate(&mut OsRng);
+                Argon2::default()
+                    .hash_password(password.as_bytes(), &salt)
+                    .map(|hashed| hashed.to_string())
+                    .map_err(|e| format!("Failed to hash password: {}", e))
+            }
+        }
+    }
+
+    fn verify(&self, password: &str, stored_hash: &str) -> Result<bool, String> {
+        match self {
+            PasswordScheme::Bcrypt => verify(password, stored_hash).map_err(|e| format!("Failed to verify password: {}", e)),
+            PasswordScheme::Argon2id => {
+                let parsed = PasswordHash::new(stored_hash).map_err(|e| format!("Failed to parse password hash: {}", e))?;
+                Ok(Argon2::default().verify_password(password.as_bytes(), &parsed).is_ok())
+            }
+        }
+    }
+}
+
+fn hash_password(password: &str) -> Result<String, String> {
+    PasswordScheme::CURRENT.hash(password)
+}
+
+fn verify_password(password: &str, stored_hash: &str) -> Result<bool, String> {
+    match PasswordScheme::detect(stored_hash) {
+        Some(scheme) => scheme.verify(password, stored_hash),
+        None => Err("Unknown password hash format".to_string()),
+    }
+}
+
+// Compares without stopping at the first differing byte, so timing does not reveal how much matched.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |difference, (x, y)| difference | (x ^ y)) == 0
+}
+
+fn needs_rehash(stored_hash: &str) -> bool {
+    PasswordScheme::detect(stored_hash) != Some(PasswordScheme::CURRENT)
+}
+
+const KNOWN_ROLES: [&str; 4] = ["Student", "Assistant", "Subject Development", "Exam Coordinator"];
+const EXAM_COORDINATOR: &str = "Exam Coordinator";
+const SUBJECT_DEVELOPMENT: &str = "Subject Development";
+const ASSISTANT: &str = "Assistant";
+
+// A grant is active when today falls inside its optional validity window.
+const ACTIVE_GRANT_CONDITION: &str = "(valid_from IS NULL OR valid_from <= CURDATE()) AND (valid_until IS NULL OR valid_until >= CURDATE())";
+
+const DEFAULT_PAGE_SIZE: u32 = 50;
+const MAX_PAGE_SIZE: u32 = 500;
+
+const CALENDAR_TIMEZONE: &str = "Asia/Jakarta";
+
+const REMOTE_AUTH_TIMEOUT_SECS: u64 = 3;
+
+const PASSWORD_RESET_TTL_MINUTES: u32 = 30;
+const PASSWORD_RESET_MAX_ATTEMPTS: u32 = 5;
+const PASSWORD_RESET_CODE_LENGTH: usize = 8;
+// Codes are read out or typed by hand, so they use a single case.
+const PASSWORD_RESET_CHARSET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+struct MySQLConfig {
+    user: String,
+    password: String,
+    host: String,
+    database: String,
+}
+
+impl MySQLConfig {
+    fn new(user: String, password: String, host: String, database: String) -> Self {
+        Self {
+            user,
+            password,
+            host,
+            database,
+        }
+    }
+
+    fn format_url(&self) -> String {
+        format!(
+            "mysql://{}:{}@{}/{}",
+            self.user, self.password, self.host, self.database
+        )
+    }
+}
+
+#[tauri::command]
+async fn login(
+    name: String,
+    password: String,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<Option<String>, String> {
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+    let is_nim = name.chars().all(char::is_numeric);
+
+    let query = "SELECT bn_number, name, major, initial, nim, role, password FROM users WHERE nim = :name OR initial = :name";
+    let params = params! { "name" => name.clone() };
+    let result: Option<(String, String, String, Option<String>, String, String, Option<String>)> = conn.exec_first(query, params).map_err(|e| format!("Failed to execute query: {}", e))?;
+
+    if let Some((bn_number, name, major, initial, nim, role, stored_password)) = result {
+        let identity = LoginIdentity {
+            nim: nim.clone(),
+            initial: initial.clone(),
+            is_nim,
+            stored_password,
+        };
+
+        let has_local_credential = identity.stored_password.as_deref().is_some_and(|h| !h.is_empty());
+
+        let mut accepted_by = None;
+        for authenticator in current_user.authenticators.iter() {
+            match authenticator.authenticate(&identity, &password).await? {
+                AuthOutcome::Accepted => {
+                    accepted_by = Some(authenticator.name());
+                    break;
+                }
+                AuthOutcome::Rejected => return Ok(None),
+                AuthOutcome::Unavailable => continue,
+            }
+        }
+
+        // Accounts that never had a password keep working while the remote is unreachable.
+        let Some(source) = accepted_by.or((!has_local_credential).then_some("local")) else {
+            return Ok(None);
+        };
+
+        // Every remote login refreshes the local copy, so the fallback follows central password changes.
+        // A local login only upgrades the stored hash to the current scheme.
+        let refresh_local_hash = source == "remote"
+            || identity.stored_password.as_deref().is_some_and(|stored_hash| !stored_hash.is_empty() && needs_rehash(stored_hash));
+
+        if refresh_local_hash {
+            let upgraded_hash = hash_password(&password)?;
+            conn.exec_drop(
+                "UPDATE users SET password = :password WHERE bn_number = :bn_number",
+                params! { "password" => upgraded_hash, "bn_number" => &bn_number },
+            ).map_err(|e| format!("Failed to upgrade password hash: {}", e))?;
+        }
+
+        let mut roles = load_active_roles(&mut conn, &bn_number)?;
+        if roles.is_empty() && !role.is_empty() {
+            roles.push(role.clone());
+        }
+
+        let user = User {
+            bn_number: bn_number.into(),
+            nim: nim.clone(),
+            name,
+            major,
+            role,
+            initial,
+        };
+
+        match current_user.user.lock() {
+            Ok(mut user_lock) => *user_lock = Some(CurrentUser { user, roles }),
+            Err(e) => return Err(format!("Failed to lock mutex: {}", e)),
+        };
+
+        return Ok(Some(if is_nim { "nim" } else { "initial" }.to_string()));
+    }
+    Ok(None)
+}
+
+#[tauri::command]
+fn change_password(
+    old_password: String,
+    new_password: String,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<bool, String> {
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    let user_guard = current_user.user.lock().map_err(|e| format!("Failed to lock mutex: {}", e))?;
+
+    if let Some(current_user) = &*user_guard {
+        let nim = &current_user.user.nim;
+        let initial = &current_user.user.initial;
+
+        let query = if let Some(initial) = initial {
+            "SELECT password FROM users WHERE nim = :nim OR initial = :initial"
+        } else {
+            "SELECT password FROM users WHERE nim = :nim"
+        };
+
+        let params = if let Some(initial) = initial {
+            params! { "nim" => nim, "initial" => initial }
+        } else {
+            params! { "nim" => nim }
+        };
+
+        let stored_password: Option<String> = conn.exec_first(query, params).map_err(|e| format!("Failed to execute query: {}", e))?;
+
+        if let Some(stored_password) = stored_password {
+            if stored_password.is_empty() || verify_password(&old_password, &stored_password)? {
+                if !new_password.is_empty() {
+                    let bn_number = current_user.user.bn_number.inner().to_string();
+                    let policy = load_password_policy(&mut conn)?;
+                    validate_password(&policy, &new_password, &current_user.user)?;
+
+                    if is_password_reused(&mut conn, &bn_number, &stored_password, &new_password, policy.history_size)? {
+                        return Err(format!("New password must differ from the last {} passwords", policy.history_size));
+                    }
+
+                    let hashed_new_password = hash_password(&new_password)?;
+
+                    let update_query = if let Some(initial) = initial {
+                        "UPDATE users SET password = :new_password WHERE nim = :nim OR initial = :initial"
+                    } else {
+                        "UPDATE users SET password = :new_password WHERE nim = :nim"
+                    };
+
+                    let update_params = if let Some(initial) = initial {
+                        params! { "nim" => nim, "initial" => initial, "new_password" => &hashed_new_password }
+                    } else {
+                        params! { "nim" => nim, "new_password" => &hashed_new_password }
+                    };
+
+                    let mut transaction = conn.start_transaction(TxOpts::default())
+                        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+                    transaction.exec_drop(update_query, update_params).map_err(|e| format!("Failed to update password: {}", e))?;
+                    record_password_history(&mut transaction, &bn_number, &hashed_new_password)?;
+
+                    transaction.commit()
+                        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+                    return Ok(true);
+                } else {
+                    return Err("New password cannot be empty".to_string());
+                }
+            } else {
+                return Ok(false);
+            }
+        } else {
+            return Err("Stored password is NULL".to_string());
+        }
+    }
+    Err("Current user not authenticated".to_string())
+}
+
+fn require_role(state: &AppState, roles: &[&str]) -> Result<User, String> {
+    let user_guard = state.user.lock().map_err(|e| format!("Failed to lock mutex: {}", e))?;
+
+    match &*user_guard {
+        Some(current_user) if current_user.roles.iter().any(|role| roles.contains(&role.as_str())) => Ok(current_user.user.clone()),
+        Some(_) => Err("You are not allowed to perform this action".to_string()),
+        None => Err("Current user not authenticated".to_string()),
+    }
+}
+
+fn load_password_policy(conn: &mut PooledConn) -> Result<PasswordPolicy, String> {
+    let row: Option<(u32, bool, bool, bool, bool, u32, bool)> = conn.exec_first(
+        "SELECT min_length, require_uppercase, require_lowercase, require_digit, require_symbol, history_size, forbid_personal_info FROM password_policy WHERE id = 1",
+        (),
+    ).map_err(|e| format!("Failed to load password policy: {}", e))?;
+
+    Ok(match row {
+        Some((min_length, require_uppercase, require_lowercase, require_digit, require_symbol, history_size, forbid_personal_info)) => PasswordPolicy {
+            min_length,
+            require_uppercase,
+            require_lowercase,
+            require_digit,
+            require_symbol,
+            history_size,
+            forbid_personal_info,
+        },
+        None => PasswordPolicy::default(),
+    })
+}
+
+fn validate_password(policy: &PasswordPolicy, password: &str, user: &User) -> Result<(), String> {
+    let mut problems: Vec<String> = Vec::new();
+
+    if password.chars().count() < policy.min_length as usize {
+        problems.push(format!("at least {} characters", policy.min_length));
+    }
+    if policy.require_uppercase && !password.chars().any(char::is_uppercase) {
+        problems.push("an uppercase letter".to_string());
+    }
+    if policy.require_lowercase && !password.chars().any(char::is_lowercase) {
+        problems.push("a lowercase letter".to_string());
+    }
+    if policy.require_digit && !password.chars().any(|c| c.is_ascii_digit()) {
+        problems.push("a digit".to_string());
+    }
+    if policy.require_symbol && !password.chars().any(|c| !c.is_alphanumeric()) {
+        problems.push("a symbol".to_string());
+    }
+
+    let mut violations: Vec<String> = Vec::new();
+    if !problems.is_empty() {
+        violations.push(format!("Password must contain {}", problems.join(", ")));
+    }
+
+    if policy.forbid_personal_info {
+        let lowered = password.to_lowercase();
+        // Short name fragments like "Li" would reject too many passwords, so only check words of 3+ letters.
+        let mut personal_info = std::iter::once(user.nim.as_str())
+            .chain(user.initial.as_deref())
+            .chain(user.name.split_whitespace())
+            .filter(|part| part.chars().count() >= 3);
+
+        if personal_info.any(|part| lowered.contains(&part.to_lowercase())) {
+            violations.push("Password must not contain your NIM, initial or name".to_string());
+        }
+    }
+
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations.join("; "))
+    }
+}
+
+// Hashes in a scheme this build cannot verify (e.g. legacy plaintext rows) never count as a match.
+fn matches_previous_password(password: &str, previous_hashes: &[String]) -> Result<bool, String> {
+    for previous_hash in previous_hashes {
+        if let Some(scheme) = PasswordScheme::detect(previous_hash) {
+            if scheme.verify(password, previous_hash)? {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
+fn is_password_reused(
+    conn: &mut PooledConn,
+    bn_number: &str,
+    current_hash: &str,
+    new_password: &str,
+    history_size: u32,
+) -> Result<bool, String> {
+    if history_size == 0 {
+        return Ok(false);
+    }
+
+    let mut previous_hashes: Vec<String> = conn.exec(
+        "SELECT password_hash FROM password_history WHERE bn_number = :bn_number ORDER BY changed_at DESC LIMIT :limit",
+        params! { "bn_number" => bn_number, "limit" => history_size },
+    ).map_err(|e| format!("Failed to load password history: {}", e))?;
+
+    if !current_hash.is_empty() {
+        previous_hashes.push(current_hash.to_string());
+    }
+
+    matches_previous_password(new_password, &previous_hashes)
+}
+
+fn record_password_history(conn: &mut impl Queryable, bn_number: &str, password_hash: &str) -> Result<(), String> {
+    conn.exec_drop(
+        "INSERT INTO password_history (bn_number, password_hash) VALUES (:bn_number, :password_hash)",
+        params! { "bn_number" => bn_number, "password_hash" => password_hash },
+    ).map_err(|e| format!("Failed to record password history: {}", e))
+}
+
+#[tauri::command]
+fn get_password_policy(mysql_pool: State<'_, Pool>) -> Result<PasswordPolicy, String> {
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+    load_password_policy(&mut conn)
+}
+
+#[tauri::command]
+fn update_password_policy(
+    policy: PasswordPolicy,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<(), String> {
+    require_role(&current_user, &[EXAM_COORDINATOR])?;
+
+    if policy.min_length == 0 {
+        return Err("Minimum password length must be at least 1".to_string());
+    }
+
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    conn.exec_drop(
+        r"REPLACE INTO password_policy (
+            id, min_length, require_uppercase, require_lowercase, require_digit,
+            require_symbol, history_size, forbid_personal_info
+        ) VALUES (
+            1, :min_length, :require_uppercase, :require_lowercase, :require_digit,
+            :require_symbol, :history_size, :forbid_personal_info
+        )",
+        params! {
+            "min_length" => policy.min_length,
+            "require_uppercase" => policy.require_uppercase,
+            "require_lowercase" => policy.require_lowercase,
+            "require_digit" => policy.require_digit,
+            "require_symbol" => policy.require_symbol,
+            "history_size" => policy.history_size,
+            "forbid_personal_info" => policy.forbid_personal_info,
+        },
+    ).map_err(|e| format!("Failed to update password policy: {}", e))?;
+
+    Ok(())
+}
+
+fn record_audit(conn: &mut impl Queryable, actor: &str, action: &str, target: &str, detail: &str) -> Result<(), String> {
+    conn.exec_drop(
+        "INSERT INTO audit_log (actor, action, target, detail) VALUES (:actor, :action, :target, :detail)",
+        params! { "actor" => actor, "action" => action, "target" => target, "detail" => detail },
+    ).map_err(|e| format!("Failed to write audit log: {}", e))
+}
+
+#[tauri::command]
+fn issue_password_reset(
+    bn_number: String,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<String, String> {
+    let coordinator = require_role(&current_user, &[EXAM_COORDINATOR])?;
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    let user_exists: Option<String> = conn.exec_first(
+        "SELECT bn_number FROM users WHERE bn_number = :bn_number",
+        params! { "bn_number" => &bn_number },
+    ).map_err(|e| format!("Failed to execute query: {}", e))?;
+
+    if user_exists.is_none() {
+        return Err(format!("User {} not found", bn_number));
+    }
+
+    let mut rng = rand::thread_rng();
+    let code: String = (0..PASSWORD_RESET_CODE_LENGTH)
+        .map(|_| char::from(PASSWORD_RESET_CHARSET[rng.gen_range(0..PASSWORD_RESET_CHARSET.len())]))
+        .collect();
+    let code_hash = hash_password(&code)?;
+
+    let mut transaction = conn.start_transaction(TxOpts::default())
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    // Only the most recently issued code is valid.
+    transaction.exec_drop(
+        "UPDATE password_reset SET used_at = NOW() WHERE bn_number = :bn_number AND used_at IS NULL",
+        params! { "bn_number" => &bn_number },
+    ).map_err(|e| format!("Failed to revoke previous reset codes: {}", e))?;
+
+    transaction.exec_drop(
+        r"INSERT INTO password_reset (bn_number, code_hash, issued_by, expires_at)
+        VALUES (:bn_number, :code_hash, :issued_by, NOW() + INTERVAL :ttl MINUTE)",
+        params! {
+            "bn_number" => &bn_number,
+            "code_hash" => code_hash,
+            "issued_by" => coordinator.bn_number.inner(),
+            "ttl" => PASSWORD_RESET_TTL_MINUTES,
+        },
+    ).map_err(|e| format!("Failed to store reset code: {}", e))?;
+
+    record_audit(
+        &mut transaction,
+        coordinator.bn_number.inner(),
+        "password_reset_issued",
+        &bn_number,
+        &format!("expires in {} minutes", PASSWORD_RESET_TTL_MINUTES),
+    )?;
+
+    transaction.commit()
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    Ok(code)
+}
+
+#[tauri::command]
+fn redeem_password_reset(
+    name: String,
+    code: String,
+    new_password: String,
+    mysql_pool: State<'_, Pool>,
+) -> Result<bool, String> {
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    let result: Option<(String, String, String, Option<String>, String, String, Option<String>)> = conn.exec_first(
+        "SELECT bn_number, name, major, initial, nim, role, password FROM users WHERE nim = :name OR initial = :name",
+        params! { "name" => &name },
+    ).map_err(|e| format!("Failed to execute query: {}", e))?;
+
+    let Some((bn_number, user_name, major, initial, nim, role, stored_password)) = result else {
+        return Ok(false);
+    };
+
+    let pending: Option<(u64, String, u32)> = conn.exec_first(
+        r"SELECT id, code_hash, attempts FROM password_reset
+        WHERE bn_number = :bn_number AND used_at IS NULL AND expires_at > NOW()
+        ORDER BY id DESC LIMIT 1",
+        params! { "bn_number" => &bn_number },
+    ).map_err(|e| format!("Failed to execute query: {}", e))?;
+
+    let Some((reset_id, code_hash, attempts)) = pending else {
+        record_audit(&mut conn, &bn_number, "password_reset_rejected", &bn_number, "no active reset code")?;
+        return Ok(false);
+    };
+
+    if attempts >= PASSWORD_RESET_MAX_ATTEMPTS || !verify_password(&code.trim().to_uppercase(), &code_hash)? {
+        conn.exec_drop(
+            "UPDATE password_reset SET attempts = attempts + 1 WHERE id = :id",
+            params! { "id" => reset_id },
+        ).map_err(|e| format!("Failed to update reset code: {}", e))?;
+        record_audit(&mut conn, &bn_number, "password_reset_rejected", &bn_number, "invalid reset code")?;
+        return Ok(false);
+    }
+
+    let user = User {
+        bn_number: bn_number.clone().into(),
+        nim,
+        name: user_name,
+        major,
+        role,
+        initial,
+    };
+    let policy = load_password_policy(&mut conn)?;
+    validate_password(&policy, &new_password, &user)?;
+
+    if is_password_reused(&mut conn, &bn_number, stored_password.as_deref().unwrap_or_default(), &new_password, policy.history_size)? {
+        return Err(format!("New password must differ from the last {} passwords", policy.history_size));
+    }
+
+    let hashed_new_password = hash_password(&new_password)?;
+
+    let mut transaction = conn.start_transaction(TxOpts::default())
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    transaction.exec_drop(
+        "UPDATE users SET password = :password WHERE bn_number = :bn_number",
+        params! { "password" => &hashed_new_password, "bn_number" => &bn_number },
+    ).map_err(|e| format!("Failed to update password: {}", e))?;
+
+    transaction.exec_drop(
+        "UPDATE password_reset SET used_at = NOW() WHERE id = :id",
+        params! { "id" => reset_id },
+    ).map_err(|e| format!("Failed to consume reset code: {}", e))?;
+
+    record_password_history(&mut transaction, &bn_number, &hashed_new_password)?;
+    record_audit(&mut transaction, &bn_number, "password_reset_redeemed", &bn_number, "")?;
+
+    transaction.commit()
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    Ok(true)
+}
+
+fn load_active_roles(conn: &mut PooledConn, bn_number: &str) -> Result<Vec<String>, String> {
+    conn.exec(
+        format!("SELECT role_name FROM user_roles WHERE bn_number = :bn_number AND {} ORDER BY role_name", ACTIVE_GRANT_CONDITION),
+        params! { "bn_number" => bn_number },
+    ).map_err(|e| format!("Failed to load roles: {}", e))
+}
+
+fn ensure_known_role(role_name: &str) -> Result<(), String> {
+    if KNOWN_ROLES.contains(&role_name) {
+        Ok(())
+    } else {
+        Err(format!("Unknown role: {}", role_name))
+    }
+}
+
+fn ensure_other_coordinator_exists(conn: &mut PooledConn, bn_number: &str) -> Result<(), String> {
+    let remaining: Option<u64> = conn.exec_first(
+        format!(
+            "SELECT COUNT(DISTINCT bn_number) FROM user_roles WHERE role_name = :role_name AND bn_number <> :bn_number AND {}",
+            ACTIVE_GRANT_CONDITION
+        ),
+        params! { "role_name" => EXAM_COORDINATOR, "bn_number" => bn_number },
+    ).map_err(|e| format!("Failed to count exam coordinators: {}", e))?;
+
+    if remaining.unwrap_or(0) == 0 {
+        return Err("Cannot remove the last Exam Coordinator".to_string());
+    }
+    Ok(())
+}
+
+fn record_role_change(
+    conn: &mut impl Queryable,
+    bn_number: &str,
+    role_name: &str,
+    action: &str,
+    valid_from: Option<&str>,
+    valid_until: Option<&str>,
+    changed_by: &str,
+) -> Result<(), String> {
+    conn.exec_drop(
+        r"INSERT INTO role_history (bn_number, role_name, action, valid_from, valid_until, changed_by)
+        VALUES (:bn_number, :role_name, :action, :valid_from, :valid_until, :changed_by)",
+        params! {
+            "bn_number" => bn_number,
+            "role_name" => role_name,
+            "action" => action,
+            "valid_from" => valid_from,
+            "valid_until" => valid_until,
+            "changed_by" => changed_by,
+        },
+    ).map_err(|e| format!("Failed to record role change: {}", e))
+}
+
+#[tauri::command]
+fn get_roles() -> Vec<String> {
+    KNOWN_ROLES.iter().map(|role| role.to_string()).collect()
+}
+
+#[tauri::command]
+fn get_user_roles(bn_number: String, mysql_pool: State<'_, Pool>) -> Result<Vec<RoleGrant>, String> {
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    conn.exec_map(
+        r"SELECT bn_number, role_name, DATE_FORMAT(valid_from, '%Y-%m-%d'), DATE_FORMAT(valid_until, '%Y-%m-%d'), granted_by
+        FROM user_roles WHERE bn_number = :bn_number ORDER BY role_name",
+        params! { "bn_number" => bn_number },
+        |(bn_number, role_name, valid_from, valid_until, granted_by)| RoleGrant {
+            bn_number,
+            role_name,
+            valid_from,
+            valid_until,
+            granted_by,
+        },
+    ).map_err(|e| format!("Failed to fetch user roles: {}", e))
+}
+
+#[tauri::command]
+fn get_role_history(bn_number: String, mysql_pool: State<'_, Pool>) -> Result<Vec<RoleChange>, String> {
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    conn.exec_map(
+        r"SELECT bn_number, role_name, action, DATE_FORMAT(valid_from, '%Y-%m-%d'), DATE_FORMAT(valid_until, '%Y-%m-%d'),
+            changed_by, DATE_FORMAT(changed_at, '%Y-%m-%d %H:%i:%s')
+        FROM role_history WHERE bn_number = :bn_number ORDER BY changed_at DESC, id DESC",
+        params! { "bn_number" => bn_number },
+        |(bn_number, role_name, action, valid_from, valid_until, changed_by, changed_at)| RoleChange {
+            bn_number,
+            role_name,
+            action,
+            valid_from,
+            valid_until,
+            changed_by,
+            changed_at,
+        },
+    ).map_err(|e| format!("Failed to fetch role history: {}", e))
+}
+
+#[tauri::command]
+fn grant_role(
+    bn_number: String,
+    role_name: String,
+    valid_from: Option<String>,
+    valid_until: Option<String>,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<(), String> {
+    let coordinator = require_role(&current_user, &[EXAM_COORDINATOR])?;
+    ensure_known_role(&role_name)?;
+
+    if let (Some(from), Some(until)) = (&valid_from, &valid_until) {
+        if from > until {
+            return Err("Role validity must start before it ends".to_string());
+        }
+    }
+
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    // Re-granting with a window that excludes today takes the role away just like a revoke.
+    if role_name == EXAM_COORDINATOR {
+        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+        let active_today = valid_from.as_deref().is_none_or(|from| from <= today.as_str())
+            && valid_until.as_deref().is_none_or(|until| until >= today.as_str());
+        if !active_today {
+            ensure_other_coordinator_exists(&mut conn, &bn_number)?;
+        }
+    }
+
+    conn.exec_drop(
+        r"INSERT INTO user_roles (bn_number, role_name, valid_from, valid_until, granted_by)
+        VALUES (:bn_number, :role_name, :valid_from, :valid_until, :granted_by)
+        ON DUPLICATE KEY UPDATE valid_from = VALUES(valid_from), valid_until = VALUES(valid_until), granted_by = VALUES(granted_by)",
+        params! {
+            "bn_number" => &bn_number,
+            "role_name" => &role_name,
+            "valid_from" => &valid_from,
+            "valid_until" => &valid_until,
+            "granted_by" => coordinator.bn_number.inner(),
+        },
+    ).map_err(|e| format!("Failed to grant role: {}", e))?;
+
+    record_role_change(
+        &mut conn,
+        &bn_number,
+        &role_name,
+        "grant",
+        valid_from.as_deref(),
+        valid_until.as_deref(),
+        coordinator.bn_number.inner(),
+    )
+}
+
+#[tauri::command]
+fn revoke_role(
+    bn_number: String,
+    role_name: String,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<(), String> {
+    let coordinator = require_role(&current_user, &[EXAM_COORDINATOR])?;
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    if role_name == EXAM_COORDINATOR {
+        ensure_other_coordinator_exists(&mut conn, &bn_number)?;
+    }
+
+    let mut transaction = conn.start_transaction(TxOpts::default())
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    transaction.exec_drop(
+        "DELETE FROM user_roles WHERE bn_number = :bn_number AND role_name = :role_name",
+        params! { "bn_number" => &bn_number, "role_name" => &role_name },
+    ).map_err(|e| format!("Failed to revoke role: {}", e))?;
+    if transaction.affected_rows() == 0 {
+        return Err(format!("User {} does not hold the {} role", bn_number, role_name));
+    }
+
+    // users.role is backfilled into user_roles and used as the login fallback, so it has to move to a
+    // remaining grant (or to no role at all) or the revoked role would come back.
+    transaction.exec_drop(
+        format!(
+            r"UPDATE users SET role = COALESCE(
+                (SELECT role_name FROM user_roles WHERE bn_number = :bn_number AND {} ORDER BY role_name LIMIT 1),
+                ''
+            )
+            WHERE bn_number = :bn_number AND role = :role_name",
+            ACTIVE_GRANT_CONDITION
+        ),
+        params! { "bn_number" => &bn_number, "role_name" => &role_name },
+    ).map_err(|e| format!("Failed to update user role: {}", e))?;
+
+    record_role_change(&mut transaction, &bn_number, &role_name, "revoke", None, None, coordinator.bn_number.inner())?;
+
+    transaction.commit()
+        .map_err(|e| format!("Failed to commit transaction: {}", e))
+}
+
+// Moves the user's primary role (users.role) to `new_role` and records the change. Only the grant of
+// the previous primary role is replaced; roles added through grant_role are kept.
+fn apply_primary_role(conn: &mut PooledConn, bn_number: &str, new_role: &str, changed_by: &str) -> Result<(), String> {
+    ensure_known_role(new_role)?;
+
+    let previous_role: Option<Option<String>> = conn.exec_first(
+        "SELECT role FROM users WHERE bn_number = :bn_number",
+        params! { "bn_number" => bn_number },
+    ).map_err(|e| format!("Failed to execute query: {}", e))?;
+    let Some(previous_role) = previous_role else {
+        return Err(format!("User {} not found", bn_number));
+    };
+    let previous_role = previous_role.filter(|role| !role.is_empty() && role != new_role);
+
+    let active_roles = load_active_roles(conn, bn_number)?;
+    if previous_role.as_deref() == Some(EXAM_COORDINATOR) && active_roles.iter().any(|role| role == EXAM_COORDINATOR) {
+        ensure_other_coordinator_exists(conn, bn_number)?;
+    }
+
+    let mut transaction = conn.start_transaction(TxOpts::default())
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    transaction.exec_drop(
+        "UPDATE users SET role = :role WHERE bn_number = :bn_number",
+        params! {
+            "role" => new_role,
+            "bn_number" => bn_number,
+        },
+    )
+    .map_err(|e| format!("Failed to update user role: {}", e))?;
+
+    if let Some(previous_role) = &previous_role {
+        transaction.exec_drop(
+            "DELETE FROM user_roles WHERE bn_number = :bn_number AND role_name = :role_name",
+            params! { "bn_number" => bn_number, "role_name" => previous_role },
+        ).map_err(|e| format!("Failed to revoke previous role: {}", e))?;
+
+        if active_roles.contains(previous_role) {
+            record_role_change(&mut transaction, bn_number, previous_role, "revoke", None, None, changed_by)?;
+        }
+    }
+
+    transaction.exec_drop(
+        r"INSERT IGNORE INTO user_roles (bn_number, role_name, granted_by)
+        VALUES (:bn_number, :role_name, :granted_by)",
+        params! {
+            "bn_number" => bn_number,
+            "role_name" => new_role,
+            "granted_by" => changed_by,
+        },
+    ).map_err(|e| format!("Failed to grant role: {}", e))?;
+
+    if !active_roles.iter().any(|role| role == new_role) {
+        record_role_change(&mut transaction, bn_number, new_role, "grant", None, None, changed_by)?;
+    }
+
+    transaction.commit()
+        .map_err(|e| format!("Failed to commit transaction: {}", e))
+}
+
+#[tauri::command]
+fn edit_role(
+    bn_number: String,
+    new_role: String,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<(), String> {
+    let coordinator = require_role(&current_user, &[EXAM_COORDINATOR])?;
+
+    let mut conn: PooledConn = mysql_pool
+        .get_conn()
+        .map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    apply_primary_role(&mut conn, &bn_number, &new_role, coordinator.bn_number.inner())
+}
+
+// One listing covers every remote user, including those outside the known roles.
+async fn fetch_remote_roles(client: &GraphQlClient) -> Result<HashMap<String, String>, String> {
+    Ok(fetch_all_users(client)
+        .await
+        .map_err(|e| format!("Failed to fetch remote roles: {}", e))?
+        .into_iter()
+        .map(|user| (user.bn_number.into_inner(), user.role))
+        .collect())
+}
+
+#[tauri::command]
+async fn get_role_differences(
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+    client: State<'_, GraphQlClient>,
+) -> Result<Vec<RoleDifference>, String> {
+    require_role(&current_user, &[EXAM_COORDINATOR])?;
+
+    let remote_roles = fetch_remote_roles(&client).await?;
+
+    let local_users: Vec<(String, Option<String>, String, Option<String>, Option<String>)> = {
+        let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+        conn.query("SELECT bn_number, nim, name, initial, role FROM users")
+            .map_err(|e| format!("Failed to execute query: {}", e))?
+    };
+
+    // Pairs the coordinator already settled in favour of the local role are not reported again.
+    // remote_role is NULL when the user had no remote role at the time.
+    let kept_local: Vec<(String, String, Option<String>)> = {
+        let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+        conn.query("SELECT bn_number, local_role, remote_role FROM role_reconciliation WHERE winner = 'local'")
+            .map_err(|e| format!("Failed to execute query: {}", e))?
+    };
+
+    let mut differences = Vec::new();
+    for (bn_number, nim, name, initial, local_role) in local_users {
+        let local_role = local_role.unwrap_or_default();
+        let remote_role = remote_roles.get(&bn_number).cloned();
+
+        if remote_role.as_deref() == Some(local_role.as_str()) {
+            continue;
+        }
+
+        let already_kept = kept_local.iter().any(|(kept_bn, kept_local_role, kept_remote_role)| {
+            kept_bn == &bn_number && kept_local_role == &local_role && kept_remote_role.as_deref() == remote_role.as_deref()
+        });
+        if already_kept {
+            continue;
+        }
+
+        differences.push(RoleDifference {
+            bn_number,
+            nim: nim.unwrap_or_default(),
+            name,
+            initial,
+            local_role,
+            remote_role,
+        });
+    }
+
+    Ok(differences)
+}
+
+#[tauri::command]
+async fn resolve_role_difference(
+    bn_number: String,
+    winner: RoleSource,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+    client: State<'_, GraphQlClient>,
+) -> Result<(), String> {
+    let coordinator = require_role(&current_user, &[EXAM_COORDINATOR])?;
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    let local_role: Option<Option<String>> = conn.exec_first(
+        "SELECT role FROM users WHERE bn_number = :bn_number",
+        params! { "bn_number" => &bn_number },
+    ).map_err(|e| format!("Failed to execute query: {}", e))?;
+
+    let Some(local_role) = local_role else {
+        return Err(format!("User {} not found", bn_number));
+    };
+    let local_role = local_role.unwrap_or_default();
+
+    // Resolved against the same listing get_role_differences reported from.
+    let remote_role = fetch_remote_roles(&client).await?.remove(&bn_number);
+
+    if winner == RoleSource::Remote {
+        let Some(remote_role) = &remote_role else {
+            return Err(format!("No remote role found for {}", bn_number));
+        };
+        apply_primary_role(&mut conn, &bn_number, remote_role, coordinator.bn_number.inner())?;
+    }
+
+    let winner_name = match winner {
+        RoleSource::Local => "local",
+        RoleSource::Remote => "remote",
+    };
+
+    conn.exec_drop(
+        r"INSERT INTO role_reconciliation (bn_number, local_role, remote_role, winner, decided_by)
+        VALUES (:bn_number, :local_role, :remote_role, :winner, :decided_by)",
+        params! {
+            "bn_number" => &bn_number,
+            "local_role" => &local_role,
+            "remote_role" => &remote_role,
+            "winner" => winner_name,
+            "decided_by" => coordinator.bn_number.inner(),
+        },
+    ).map_err(|e| format!("Failed to record reconciliation decision: {}", e))?;
+
+    record_audit(
+        &mut conn,
+        coordinator.bn_number.inner(),
+        "role_reconciled",
+        &bn_number,
+        &format!("local={} remote={} winner={}", local_role, remote_role.as_deref().unwrap_or("none"), winner_name),
+    )
+}
+
+#[tauri::command]
+async fn update_exam_transaction(
+  transaction_id: String,
+  proctor: String,
+  status: String,
+  mysql_pool: State<'_, Pool>,
+  current_user: State<'_, AppState>,
+) -> Result<Vec<TravelWarning>, String> {
+  require_role(&current_user, &[EXAM_COORDINATOR])?;
+  let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+  ensure_transaction_editable(&mut conn, &transaction_id)?;
+  if !proctor.is_empty() {
+    ensure_proctor_unbooked(&mut conn, &proctor, &transaction_id)?;
+  }
+
+  conn.exec_drop(
+    "UPDATE exam_transaction SET proctor = :proctor, status = :status, revision = revision + 1 WHERE transaction_id = :transaction_id",
+    params! {
+      "transaction_id" => &transaction_id,
+      "proctor" => &proctor,
+      "status" => status,
+    },
+  ).map_err(|e| format!("Failed to update exam transaction: {}", e))?;
+
+  // The update is kept either way; the caller decides whether the proctor can make the trip.
+  let slots = load_proctor_slots(
+    &mut conn,
+    &format!(
+      r"{} = (SELECT {} FROM exam_transaction et WHERE et.transaction_id = :transaction_id)
+        AND et.transaction_date = (SELECT transaction_date FROM exam_transaction WHERE transaction_id = :transaction_id)",
+      PROCTOR_BN_SQL, PROCTOR_BN_SQL
+    ),
+    params! { "transaction_id" => &transaction_id },
+  )?;
+  let travel_times = load_travel_times(&mut conn)?;
+
+  Ok(travel_warnings(slots, &travel_times)
+    .into_iter()
+    .filter(|warning| warning.from_exam == transaction_id || warning.to_exam == transaction_id)
+    .collect())
+}
+
+#[tauri::command]
+fn get_current_user(state: State<'_, AppState>) -> Option<CurrentUser> {
+    state.user.lock().unwrap().clone()
+}
+
+async fn fetch_all_users(client: &GraphQlClient) -> Result<Vec<User>, GraphQlClientError> {
+    client.run(|| UsersQuery::build(())).await.map(|data| data.get_all_user)
+}
+
+async fn fetch_all_subjects(client: &GraphQlClient) -> Result<Vec<Subject>, GraphQlClientError> {
+    client.run(|| GetAllSubject::build(())).await.map(|data| data.get_all_subject)
+}
+
+async fn fetch_all_rooms(client: &GraphQlClient) -> Result<Vec<Room>, GraphQlClientError> {
+    client.run(|| GetAllRoom::build(())).await.map(|data| data.get_all_room)
+}
+
+async fn fetch_all_enrollments(client: &GraphQlClient) -> Result<Vec<Enrollment>, GraphQlClientError> {
+    let data = client.run(|| GetAllEnrollment::build(())).await?;
+
+    Ok(data.get_all_enrollment
+        .unwrap_or_default()
+        .into_iter()
+        .flatten()
+        .collect())
+}
+
+fn cache_status(conn: &mut PooledConn, dataset: Dataset, ttl_minutes: u64) -> Result<(Option<String>, bool), String> {
+    let row: Option<(String, bool)> = conn.exec_first(
+        r"SELECT DATE_FORMAT(last_synced_at, '%Y-%m-%d %H:%i:%s'), last_synced_at < NOW() - INTERVAL :ttl MINUTE
+        FROM sync_state WHERE dataset = :dataset",
+        params! { "dataset" => dataset.name(), "ttl" => ttl_minutes },
+    ).map_err(|e| format!("Failed to read sync state: {}", e))?;
+
+    Ok(match row {
+        Some((last_synced_at, stale)) => (Some(last_synced_at), stale),
+        None => (None, true),
+    })
+}
+
+fn mark_synced(conn: &mut PooledConn, dataset: Dataset) -> Result<(), String> {
+    conn.exec_drop(
+        "REPLACE INTO sync_state (dataset, last_synced_at) VALUES (:dataset, NOW())",
+        params! { "dataset" => dataset.name() },
+    ).map_err(|e| format!("Failed to update sync state: {}", e))
+}
+
+async fn refresh_dataset(conn: &mut PooledConn, client: &GraphQlClient, dataset: Dataset) -> Result<(), String> {
+    match dataset {
+        Dataset::Users => {
+            insert_users(conn, client).await?;
+            backfill_user_roles(conn).map_err(|e| format!("Failed to backfill user roles: {}", e))?;
+        }
+        Dataset::Rooms => insert_room(conn, client).await?,
+        Dataset::Subjects => insert_subject(conn, client).await?,
+        Dataset::Enrollments => insert_enrollment(conn, client).await?,
+    }
+    mark_synced(conn, dataset)
+}
+
+// Refreshes one dataset from the remote unless a refresh for it is already running.
+// Failures are only logged: the local tables keep serving the last synced copy.
+async fn revalidate(app: AppHandle, dataset: Dataset) {
+    let cache = app.state::<SyncCache>();
+    let claimed = match cache.refreshing.lock() {
+        Ok(mut refreshing) => refreshing.insert(dataset),
+        Err(e) => {
+            log::warn!("Failed to lock mutex: {}", e);
+            return;
+        }
+    };
+    if !claimed {
+        return;
+    }
+
+    let result = match app.state::<Pool>().get_conn() {
+        Ok(mut conn) => refresh_dataset(&mut conn, &app.state::<GraphQlClient>(), dataset).await,
+        Err(e) => Err(format!("Failed to get connection: {}", e)),
+    };
+
+    if let Err(e) = result {
+        log::warn!("Failed to refresh {}: {}", dataset.name(), e);
+    }
+
+    match cache.refreshing.lock() {
+        Ok(mut refreshing) => {
+            refreshing.remove(&dataset);
+        }
+        Err(e) => log::warn!("Failed to lock mutex: {}", e),
+    }
+}
+
+fn cached_response<T>(app: &AppHandle, conn: &mut PooledConn, dataset: Dataset, data: Vec<T>) -> Result<Cached<T>, String> {
+    let cache = app.state::<SyncCache>();
+    let ttl_minutes = *cache.ttl_minutes.lock().map_err(|e| format!("Failed to lock mutex: {}", e))?;
+    let (last_synced_at, stale) = cache_status(conn, dataset, ttl_minutes)?;
+
+    if stale {
+        tauri::async_runtime::spawn(revalidate(app.clone(), dataset));
+    }
+
+    let refreshing = stale || cache.refreshing.lock().map_err(|e| format!("Failed to lock mutex: {}", e))?.contains(&dataset);
+    Ok(Cached {
+        data,
+        last_synced_at,
+        stale,
+        refreshing,
+    })
+}
+
+#[tauri::command]
+async fn get_all_users(app: AppHandle, mysql_pool: State<'_, Pool>) -> Result<Cached<User>, String> {
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+    let users = local_users(&mut conn, "1 = 1", mysql::Params::Empty)?;
+    cached_response(&app, &mut conn, Dataset::Users, users)
+}
+
+#[tauri::command]
+async fn get_all_subject(app: AppHandle, mysql_pool: State<'_, Pool>) -> Result<Cached<Subject>, String> {
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+    let subjects = local_subjects(&mut conn, "1 = 1", mysql::Params::Empty)?;
+    cached_response(&app, &mut conn, Dataset::Subjects, subjects)
+}
+
+#[tauri::command]
+async fn get_all_room(app: AppHandle, mysql_pool: State<'_, Pool>) -> Result<Cached<Room>, String> {
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+    let rooms = local_rooms(&mut conn, "1 = 1", mysql::Params::Empty)?;
+    cached_response(&app, &mut conn, Dataset::Rooms, rooms)
+}
+
+#[tauri::command]
+async fn get_all_enrollment(app: AppHandle, mysql_pool: State<'_, Pool>) -> Result<Cached<Enrollment>, String> {
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+    let enrollments = local_enrollments(&mut conn, "1 = 1", mysql::Params::Empty)?;
+    cached_response(&app, &mut conn, Dataset::Enrollments, enrollments)
+}
+
+#[tauri::command]
+fn get_cache_ttl(cache: State<'_, SyncCache>) -> Result<u64, String> {
+    let ttl_minutes = cache.ttl_minutes.lock().map_err(|e| format!("Failed to lock mutex: {}", e))?;
+    Ok(*ttl_minutes)
+}
+
+#[tauri::command]
+fn set_cache_ttl(ttl_minutes: u64, cache: State<'_, SyncCache>, current_user: State<'_, AppState>) -> Result<(), String> {
+    require_role(&current_user, &[EXAM_COORDINATOR])?;
+    *cache.ttl_minutes.lock().map_err(|e| format!("Failed to lock mutex: {}", e))? = ttl_minutes;
+    Ok(())
+}
+
+#[tauri::command]
+async fn refresh_cache(app: AppHandle, current_user: State<'_, AppState>) -> Result<(), String> {
+    require_role(&current_user, &[EXAM_COORDINATOR])?;
+    for dataset in Dataset::ALL {
+        revalidate(app.clone(), dataset).await;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_all_shifts(include_retired: Option<bool>, mysql_pool: State<'_, Pool>) -> Result<Vec<Shift>, String> {
+    let mut conn: PooledConn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+    let shifts_query = if include_retired.unwrap_or(false) {
+        "SELECT shift_id, start_time, end_time, retired FROM shift ORDER BY start_time"
+    } else {
+        "SELECT shift_id, start_time, end_time, retired FROM shift WHERE retired = FALSE ORDER BY start_time"
+    };
+    let shifts: Vec<Shift> = conn.query_map(shifts_query, |(shift_id, start_time, end_time, retired)| {
+        Shift {
+            shift_id,
+            start_time,
+            end_time,
+            retired,
+        }
+    }).map_err(|e| format!("Failed to fetch shifts: {}", e))?;
+
+    Ok(shifts)
+}
+
+const SHIFT_ID_MAX_LENGTH: usize = 10;
+
+// Standard exam shifts seeded into an empty shift table.
+const DEFAULT_SHIFTS: [(&str, &str, &str); 6] = [
+    ("1", "07:20:00", "09:00:00"),
+    ("2", "09:20:00", "11:00:00"),
+    ("3", "11:20:00", "13:00:00"),
+    ("4", "13:20:00", "15:00:00"),
+    ("5", "15:20:00", "17:00:00"),
+    ("6", "17:20:00", "19:00:00"),
+];
+
+fn validate_shift_id(shift_id: &str) -> Result<(), String> {
+    if shift_id.is_empty() || shift_id.len() > SHIFT_ID_MAX_LENGTH {
+        return Err(format!("Shift id must be 1 to {} characters", SHIFT_ID_MAX_LENGTH));
+    }
+    if !shift_id.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err("Shift id may only contain letters and digits".to_string());
+    }
+    Ok(())
+}
+
+// Accepts HH:MM or HH:MM:SS and returns both times as HH:MM:SS.
+fn validate_shift_times(start_time: &str, end_time: &str) -> Result<(String, String), String> {
+    let parse = |value: &str| {
+        chrono::NaiveTime::parse_from_str(value, "%H:%M:%S")
+            .or_else(|_| chrono::NaiveTime::parse_from_str(value, "%H:%M"))
+            .map_err(|_| format!("Invalid time '{}', expected HH:MM", value))
+    };
+    let start = parse(start_time)?;
+    let end = parse(end_time)?;
+
+    if start >= end {
+        return Err("Shift start time must be before its end time".to_string());
+    }
+    Ok((start.format("%H:%M:%S").to_string(), end.format("%H:%M:%S").to_string()))
+}
+
+fn ensure_no_shift_overlap(conn: &mut PooledConn, shift_id: &str, start_time: &str, end_time: &str) -> Result<(), String> {
+    let overlapping: Option<String> = conn.exec_first(
+        r"SELECT shift_id FROM shift
+        WHERE retired = FALSE AND shift_id <> :shift_id
+          AND start_time < :end_time AND :start_time < end_time
+        LIMIT 1",
+        params! { "shift_id" => shift_id, "start_time" => start_time, "end_time" => end_time },
+    ).map_err(|e| format!("Failed to check shift overlap: {}", e))?;
+
+    match overlapping {
+        Some(other) => Err(format!("Shift overlaps with shift {}", other)),
+        None => Ok(()),
+    }
+}
+
+#[tauri::command]
+fn create_shift(
+    shift_id: String,
+    start_time: String,
+    end_time: String,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<(), String> {
+    let coordinator = require_role(&current_user, &[EXAM_COORDINATOR])?;
+    validate_shift_id(&shift_id)?;
+    let (start_time, end_time) = validate_shift_times(&start_time, &end_time)?;
+
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    let exists: Option<String> = conn.exec_first(
+        "SELECT shift_id FROM shift WHERE shift_id = :shift_id",
+        params! { "shift_id" => &shift_id },
+    ).map_err(|e| format!("Failed to execute query: {}", e))?;
+    if exists.is_some() {
+        return Err(format!("Shift {} already exists", shift_id));
+    }
+    ensure_no_shift_overlap(&mut conn, &shift_id, &start_time, &end_time)?;
+
+    conn.exec_drop(
+        "INSERT INTO shift (shift_id, start_time, end_time) VALUES (:shift_id, :start_time, :end_time)",
+        params! { "shift_id" => &shift_id, "start_time" => &start_time, "end_time" => &end_time },
+    ).map_err(|e| format!("Failed to create shift: {}", e))?;
+
+    record_audit(
+        &mut conn,
+        coordinator.bn_number.inner(),
+        "shift_created",
+        &shift_id,
+        &format!("{} - {}", start_time, end_time),
+    )
+}
+
+#[tauri::command]
+fn update_shift(
+    shift_id: String,
+    start_time: String,
+    end_time: String,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<(), String> {
+    let coordinator = require_role(&current_user, &[EXAM_COORDINATOR])?;
+    let (start_time, end_time) = validate_shift_times(&start_time, &end_time)?;
+
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    let retired: Option<bool> = conn.exec_first(
+        "SELECT retired FROM shift WHERE shift_id = :shift_id",
+        params! { "shift_id" => &shift_id },
+    ).map_err(|e| format!("Failed to execute query: {}", e))?;
+    match retired {
+        None => return Err(format!("Shift {} not found", shift_id)),
+        Some(true) => return Err(format!("Shift {} is retired", shift_id)),
+        Some(false) => {}
+    }
+    ensure_no_shift_overlap(&mut conn, &shift_id, &start_time, &end_time)?;
+
+    // Moving the shift's times would rewrite exams whose period is already frozen.
+    let frozen: Option<u64> = conn.exec_first(
+        r"SELECT COUNT(*) FROM exam_transaction
+        JOIN exam_period ON exam_period.period_id = exam_transaction.period_id
+        WHERE exam_transaction.shift_id = :shift_id AND (exam_period.locked = TRUE OR exam_period.archived = TRUE)",
+        params! { "shift_id" => &shift_id },
+    ).map_err(|e| format!("Failed to execute query: {}", e))?;
+    let frozen = frozen.unwrap_or(0);
+    if frozen > 0 {
+        return Err(format!(
+            "Shift {} is used by {} exams in locked or archived periods; create a new shift instead",
+            shift_id, frozen
+        ));
+    }
+
+    let mut transaction = conn.start_transaction(TxOpts::default())
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    transaction.exec_drop(
+        "UPDATE shift SET start_time = :start_time, end_time = :end_time WHERE shift_id = :shift_id",
+        params! { "shift_id" => &shift_id, "start_time" => &start_time, "end_time" => &end_time },
+    ).map_err(|e| format!("Failed to update shift: {}", e))?;
+
+    // Exams in this shift moved in time, so calendar subscribers must see a new revision.
+    transaction.exec_drop(
+        "UPDATE exam_transaction SET revision = revision + 1 WHERE shift_id = :shift_id",
+        params! { "shift_id" => &shift_id },
+    ).map_err(|e| format!("Failed to update exam transactions: {}", e))?;
+
+    transaction.commit()
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    record_audit(
+        &mut conn,
+        coordinator.bn_number.inner(),
+        "shift_updated",
+        &shift_id,
+        &format!("{} - {}", start_time, end_time),
+    )
+}
+
+/// Retired shifts stay referenced by past exams but can no longer be scheduled.
+#[tauri::command]
+fn retire_shift(
+    shift_id: String,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<(), String> {
+    let coordinator = require_role(&current_user, &[EXAM_COORDINATOR])?;
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    let upcoming: Option<u64> = conn.exec_first(
+        "SELECT COUNT(*) FROM exam_transaction WHERE shift_id = :shift_id AND transaction_date >= CURDATE()",
+        params! { "shift_id" => &shift_id },
+    ).map_err(|e| format!("Failed to execute query: {}", e))?;
+    let upcoming = upcoming.unwrap_or(0);
+    if upcoming > 0 {
+        return Err(format!("Shift {} still has {} upcoming exams", shift_id, upcoming));
+    }
+
+    conn.exec_drop(
+        "UPDATE shift SET retired = TRUE WHERE shift_id = :shift_id",
+        params! { "shift_id" => &shift_id },
+    ).map_err(|e| format!("Failed to retire shift: {}", e))?;
+
+    if conn.affected_rows() == 0 {
+        return Err(format!("Shift {} not found or already retired", shift_id));
+    }
+
+    record_audit(&mut conn, coordinator.bn_number.inner(), "shift_retired", &shift_id, "")
+}
+
+// Serves the remote answer, or the local copy of the master data when the service is unreachable.
+fn or_local<T>(remote: Result<T, GraphQlClientError>, local: impl FnOnce() -> Result<T, String>) -> Result<T, String> {
+    match remote {
+        Ok(value) => Ok(value),
+        Err(e) if e.is_offline() => local(),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn local_users(conn: &mut PooledConn, condition: &str, params: mysql::Params) -> Result<Vec<User>, String> {
+    conn.exec_map(
+        format!("SELECT bn_number, nim, name, major, role, initial FROM users WHERE {}", condition),
+        params,
+        |(bn_number, nim, name, major, role, initial): (String, Option<String>, String, Option<String>, Option<String>, Option<String>)| User {
+            bn_number: bn_number.into(),
+            nim: nim.unwrap_or_default(),
+            name,
+            major: major.unwrap_or_default(),
+            role: role.unwrap_or_default(),
+            initial,
+        },
+    ).map_err(|e| format!("Failed to fetch users: {}", e))
+}
+
+fn local_rooms(conn: &mut PooledConn, condition: &str, params: mysql::Params) -> Result<Vec<Room>, String> {
+    conn.exec_map(
+        format!("SELECT room_number, room_capacity, campus FROM room WHERE {} ORDER BY room_number", condition),
+        params,
+        |(room_number_str, room_capacity, campus)| Room {
+            campus,
+            room_capacity,
+            room_number_str,
+        },
+    ).map_err(|e| format!("Failed to fetch rooms: {}", e))
+}
+
+fn local_subjects(conn: &mut PooledConn, condition: &str, params: mysql::Params) -> Result<Vec<Subject>, String> {
+    conn.exec_map(
+        format!("SELECT subject_code, subject_name FROM subject WHERE {} ORDER BY subject_code", condition),
+        params,
+        |(subject_code_str, subject_name)| Subject {
+            subject_code_str,
+            subject_name,
+        },
+    ).map_err(|e| format!("Failed to fetch subjects: {}", e))
+}
+
+fn local_enrollments(conn: &mut PooledConn, condition: &str, params: mysql::Params) -> Result<Vec<Enrollment>, String> {
+    conn.exec_map(
+        format!("SELECT class_code, nim, subject_code FROM enrollment WHERE {}", condition),
+        params,
+        |(class_code_str, nim, subject_code)| Enrollment {
+            class_code_str,
+            nim,
+            subject_code,
+        },
+    ).map_err(|e| format!("Failed to fetch enrollments: {}", e))
+}
+
+#[tauri::command]
+async fn get_user_by_nim(nim: String, client: State<'_, GraphQlClient>, mysql_pool: State<'_, Pool>) -> Result<User, String> {
+    let remote = client.run(|| UserByNIM::build(NimArguments { nim: nim.clone() })).await;
+
+    or_local(remote.map(|data| data.get_user_by_nim), || {
+        let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+        local_users(&mut conn, "nim = :nim", params! { "nim" => &nim })?
+            .into_iter()
+            .next()
+            .ok_or_else(|| format!("User {} not found", nim))
+    })
+}
+
+#[tauri::command]
+async fn get_user_by_initial(initial: String, client: State<'_, GraphQlClient>, mysql_pool: State<'_, Pool>) -> Result<User, String> {
+    let remote = client.run(|| UserByInitial::build(InitialArguments { initial: initial.clone() })).await;
+
+    or_local(remote.map(|data| data.get_user_by_initial), || {
+        let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+        local_users(&mut conn, "initial = :initial", params! { "initial" => &initial })?
+            .into_iter()
+            .next()
+            .ok_or_else(|| format!("User {} not found", initial))
+    })
+}
+
+#[tauri::command]
+async fn get_class_codes_by_subject_code(subject_code: String, client: State<'_, GraphQlClient>, mysql_pool: State<'_, Pool>) -> Result<Vec<String>, String> {
+    let remote = client.run(|| EnrollmentClassCodeBySubjectCode::build(SubjectCodeArguments { subject_code: subject_code.clone() })).await;
+
+    or_local(remote.map(|data| data.get_enrollment_class_code_by_subject_code.unwrap_or_default()), || {
+        let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+        conn.exec(
+            "SELECT DISTINCT class_code FROM enrollment WHERE subject_code = :subject_code ORDER BY class_code",
+            params! { "subject_code" => &subject_code },
+        ).map_err(|e| format!("Failed to fetch class codes: {}", e))
+    })
+}
+
+#[tauri::command]
+async fn get_students_by_class_and_subject_code(
+    class_code: String,
+    subject_code: String,
+    client: State<'_, GraphQlClient>,
+    mysql_pool: State<'_, Pool>,
+) -> Result<Vec<String>, String> {
+    let remote = client.run(|| StudentsByClassAndSubjectCode::build(ClassAndSubjectArguments {
+        class_code: class_code.clone(),
+        subject_code: subject_code.clone(),
+    })).await;
+
+    or_local(remote.map(|data| data.get_students_by_class_and_subject_code.unwrap_or_default()), || {
+        let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+        conn.exec(
+            "SELECT nim FROM enrollment WHERE class_code = :class_code AND subject_code = :subject_code ORDER BY nim",
+            params! { "class_code" => &class_code, "subject_code" => &subject_code },
+        ).map_err(|e| format!("Failed to fetch students: {}", e))
+    })
+}
+
+#[tauri::command]
+async fn get_enrollment_by_nim(nim: String, client: State<'_, GraphQlClient>, mysql_pool: State<'_, Pool>) -> Result<Vec<Enrollment>, String> {
+    let remote = client.run(|| EnrollmentByNIM::build(NimArguments { nim: nim.clone() })).await;
+
+    or_local(remote.map(|data| data.get_enrollment_by_nim.unwrap_or_default()), || {
+        let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+        local_enrollments(&mut conn, "nim = :nim", params! { "nim" => &nim })
+    })
+}
+
+#[tauri::command]
+async fn get_room_by_number(room_number: String, client: State<'_, GraphQlClient>, mysql_pool: State<'_, Pool>) -> Result<Room, String> {
+    let remote = client.run(|| RoomByNumber::build(RoomNumberArguments { room_number: room_number.clone() })).await;
+
+    or_local(remote.map(|data| data.get_room_by_number), || {
+        let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+        local_rooms(&mut conn, "room_number = :room_number", params! { "room_number" => &room_number })?
+            .into_iter()
+            .next()
+            .ok_or_else(|| format!("Room {} not found", room_number))
+    })
+}
+
+#[tauri::command]
+async fn get_room_by_campus(campus: String, client: State<'_, GraphQlClient>, mysql_pool: State<'_, Pool>) -> Result<Vec<Room>, String> {
+    let remote = client.run(|| RoomByCampus::build(CampusArguments { campus: campus.clone() })).await;
+
+    or_local(remote.map(|data| data.get_room_by_campus), || {
+        let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+        local_rooms(&mut conn, "campus = :campus", params! { "campus" => &campus })
+    })
+}
+
+#[tauri::command]
+async fn get_room_by_capacity(room_capacity: i32, client: State<'_, GraphQlClient>, mysql_pool: State<'_, Pool>) -> Result<Vec<Room>, String> {
+    let remote = client.run(|| RoomByCapacity::build(RoomCapacityArguments { room_capacity })).await;
+
+    or_local(remote.map(|data| data.get_room_by_capacity), || {
+        let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+        local_rooms(&mut conn, "room_capacity = :room_capacity", params! { "room_capacity" => room_capacity })
+    })
+}
+
+#[tauri::command]
+async fn get_subject_by_code(subject_code: String, client: State<'_, GraphQlClient>, mysql_pool: State<'_, Pool>) -> Result<Subject, String> {
+    let remote = client.run(|| SubjectByCode::build(SubjectCodeArguments { subject_code: subject_code.clone() })).await;
+
+    or_local(remote.map(|data| data.get_subject_by_code), || {
+        let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+        local_subjects(&mut conn, "subject_code = :subject_code", params! { "subject_code" => &subject_code })?
+            .into_iter()
+            .next()
+            .ok_or_else(|| format!("Subject {} not found", subject_code))
+    })
+}
+
+#[tauri::command]
+async fn get_subject_by_name(subject_name: String, client: State<'_, GraphQlClient>, mysql_pool: State<'_, Pool>) -> Result<Vec<Subject>, String> {
+    let remote = client.run(|| SubjectByName::build(SubjectNameArguments { subject_name: subject_name.clone() })).await;
+
+    or_local(remote.map(|data| data.get_subject_by_name), || {
+        let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+        local_subjects(
+            &mut conn,
+            "subject_name LIKE CONCAT('%', :subject_name, '%')",
+            params! { "subject_name" => &subject_name },
+        )
+    })
+}
+
+#[tauri::command]
+async fn get_room_transaction(mysql_pool: State<'_, Pool>, selected_date: String, room_number: Option<String>) -> Result<Vec<RoomTransaction>, String> {
+    let mut conn: PooledConn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+    
+    let query = match room_number {
+        Some(ref room) => "SELECT room_number, shift_id FROM exam_transaction WHERE transactionDate = :selected_date AND room_number = :room_number",
+        None => "SELECT room_number, shift_id FROM exam_transaction WHERE transactionDate = :selected_date",
+    };
+
+    let params = match room_number {
+        Some(room) => params! { "selected_date" => selected_date, "room_number" => room },
+        None => params! { "selected_date" => selected_date },
+    };
+    
+    let result: Vec<(String, String)> = conn.exec(query, params)
+        .map_err(|e| format!("Failed to execute query: {}", e))?;
+    
+    let room_transactions = result.into_iter()
+        .map(|(room_number, shift_id)| RoomTransaction {
+            room_number,
+            shift_id,
+        })
+        .collect();
+    
+    Ok(room_transactions)
+}
+
+#[tauri::command]
+async fn get_exam_transaction(period_id: Option<u32>, mysql_pool: State<'_, Pool>) -> Result<Vec<ExamTransaction>, String> {
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    let transactions: Vec<ExamTransaction> = conn.exec_map(
+        r"SELECT transaction_id, subject_code, room_number, shift_id, DATE_FORMAT(transaction_date, '%Y-%m-%d'), proctor, status, period_id, makeup_of
+        FROM exam_transaction
+        WHERE (:period_id IS NOT NULL AND period_id = :period_id)
+           OR (:period_id IS NULL AND NOT EXISTS (
+               SELECT 1 FROM exam_period WHERE exam_period.period_id = exam_transaction.period_id AND exam_period.archived = TRUE
+           ))",
+        params! { "period_id" => period_id },
+        |(transaction_id, subject_code, room_number, shift_id, transaction_date, proctor, status, period_id, makeup_of)| {
+            ExamTransaction {
+                transaction_id,
+                subject_code,
+                room_number,
+                shift_id,
+                transaction_date,
+                proctor,
+                status,
+                period_id,
+                makeup_of,
+            }
+        }
+    ).map_err(|e| format!("Failed to execute query: {}", e))?;    
+
+    Ok(transactions)
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExamPeriodType {
+    Midterm,
+    Final,
+    Retake,
+}
+
+impl ExamPeriodType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ExamPeriodType::Midterm => "midterm",
+            ExamPeriodType::Final => "final",
+            ExamPeriodType::Retake => "retake",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExamPeriod {
+    pub period_id: u32,
+    pub name: String,
+    pub period_type: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub locked: bool,
+    pub archived: bool,
+}
+
+// Date range of a period that still accepts new or changed exams.
+struct OpenPeriod {
+    period_id: u32,
+    start_date: String,
+    end_date: String,
+}
+
+fn validate_period_dates(start_date: &str, end_date: &str) -> Result<(), String> {
+    let parse = |value: &str| {
+        chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .map_err(|_| format!("Invalid date '{}', expected YYYY-MM-DD", value))
+    };
+    if parse(start_date)? > parse(end_date)? {
+        return Err("Exam period must start on or before its end date".to_string());
+    }
+    Ok(())
+}
+
+// Periods may not overlap, otherwise an exam date would belong to several periods. Archived periods
+// are done with and no longer count.
+fn ensure_no_period_overlap(conn: &mut PooledConn, period_id: Option<u32>, start_date: &str, end_date: &str) -> Result<(), String> {
+    let overlapping: Option<(String, String, String)> = conn.exec_first(
+        r"SELECT name, DATE_FORMAT(start_date, '%Y-%m-%d'), DATE_FORMAT(end_date, '%Y-%m-%d') FROM exam_period
+        WHERE archived = FALSE AND (:period_id IS NULL OR period_id <> :period_id)
+          AND start_date <= :end_date AND :start_date <= end_date
+        LIMIT 1",
+        params! { "period_id" => period_id, "start_date" => start_date, "end_date" => end_date },
+    ).map_err(|e| format!("Failed to check exam period overlap: {}", e))?;
+
+    match overlapping {
+        Some((name, other_start, other_end)) => Err(format!("Exam period overlaps with {} ({} to {})", name, other_start, other_end)),
+        None => Ok(()),
+    }
+}
+
+fn load_open_periods(conn: &mut PooledConn) -> Result<Vec<OpenPeriod>, String> {
+    conn.query_map(
+        r"SELECT period_id, DATE_FORMAT(start_date, '%Y-%m-%d'), DATE_FORMAT(end_date, '%Y-%m-%d')
+        FROM exam_period WHERE locked = FALSE ORDER BY start_date",
+        |(period_id, start_date, end_date)| OpenPeriod { period_id, start_date, end_date },
+    ).map_err(|e| format!("Failed to fetch exam periods: {}", e))
+}
+
+// Picks the open period an exam on `transaction_date` belongs to. An explicit `period_id` must cover
+// the date; otherwise exactly one open period may cover it.
+fn pick_exam_period(periods: &[OpenPeriod], period_id: Option<u32>, transaction_date: &str) -> Result<u32, String> {
+    let covers = |period: &&OpenPeriod| period.start_date.as_str() <= transaction_date && transaction_date <= period.end_date.as_str();
+
+    if let Some(period_id) = period_id {
+        let period = periods.iter()
+            .find(|period| period.period_id == period_id)
+            .ok_or_else(|| format!("Exam period {} does not exist or is locked", period_id))?;
+        if !covers(&period) {
+            return Err(format!("{} is outside exam period {} ({} to {})", transaction_date, period_id, period.start_date, period.end_date));
+        }
+        return Ok(period_id);
+    }
+
+    let matching: Vec<&OpenPeriod> = periods.iter().filter(covers).collect();
+    match matching.as_slice() {
+        [period] => Ok(period.period_id),
+        [] => Err(format!("No open exam period covers {}", transaction_date)),
+        _ => Err(format!("Several exam periods cover {}; choose one", transaction_date)),
+    }
+}
+
+fn ensure_transaction_editable(conn: &mut PooledConn, transaction_id: &str) -> Result<(), String> {
+    let locked: Option<Option<bool>> = conn.exec_first(
+        r"SELECT exam_period.locked FROM exam_transaction
+        LEFT JOIN exam_period ON exam_period.period_id = exam_transaction.period_id
+        WHERE exam_transaction.transaction_id = :transaction_id",
+        params! { "transaction_id" => transaction_id },
+    ).map_err(|e| format!("Failed to execute query: {}", e))?;
+
+    match locked {
+        None => Err(format!("Exam transaction {} not found", transaction_id)),
+        Some(Some(true)) => Err(format!("Exam transaction {} belongs to a locked exam period", transaction_id)),
+        Some(_) => Ok(()),
+    }
+}
+
+fn load_exam_period(conn: &mut PooledConn, period_id: u32) -> Result<ExamPeriod, String> {
+    conn.exec_map(
+        r"SELECT period_id, name, period_type, DATE_FORMAT(start_date, '%Y-%m-%d'), DATE_FORMAT(end_date, '%Y-%m-%d'),
+            locked, archived
+        FROM exam_period WHERE period_id = :period_id",
+        params! { "period_id" => period_id },
+        |(period_id, name, period_type, start_date, end_date, locked, archived)| ExamPeriod {
+            period_id,
+            name,
+            period_type,
+            start_date,
+            end_date,
+            locked,
+            archived,
+        },
+    ).map_err(|e| format!("Failed to fetch exam period: {}", e))?
+        .pop()
+        .ok_or_else(|| format!("Exam period {} not found", period_id))
+}
+
+#[tauri::command]
+fn get_exam_periods(include_archived: Option<bool>, mysql_pool: State<'_, Pool>) -> Result<Vec<ExamPeriod>, String> {
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    conn.exec_map(
+        r"SELECT period_id, name, period_type, DATE_FORMAT(start_date, '%Y-%m-%d'), DATE_FORMAT(end_date, '%Y-%m-%d'),
+            locked, archived
+        FROM exam_period
+        WHERE archived = FALSE OR :include_archived
+        ORDER BY start_date DESC",
+        params! { "include_archived" => include_archived.unwrap_or(false) },
+        |(period_id, name, period_type, start_date, end_date, locked, archived)| ExamPeriod {
+            period_id,
+            name,
+            period_type,
+            start_date,
+            end_date,
+            locked,
+            archived,
+        },
+    ).map_err(|e| format!("Failed to fetch exam periods: {}", e))
+}
+
+#[tauri::command]
+fn create_exam_period(
+    name: String,
+    period_type: ExamPeriodType,
+    start_date: String,
+    end_date: String,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<u32, String> {
+    let coordinator = require_role(&current_user, &[EXAM_COORDINATOR])?;
+    validate_period_dates(&start_date, &end_date)?;
+    if name.trim().is_empty() {
+        return Err("Exam period name is required".to_string());
+    }
+
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+    ensure_no_period_overlap(&mut conn, None, &start_date, &end_date)?;
+
+    conn.exec_drop(
+        r"INSERT INTO exam_period (name, period_type, start_date, end_date)
+        VALUES (:name, :period_type, :start_date, :end_date)",
+        params! {
+            "name" => name.trim(),
+            "period_type" => period_type.as_str(),
+            "start_date" => &start_date,
+            "end_date" => &end_date,
+        },
+    ).map_err(|e| format!("Failed to create exam period: {}", e))?;
+    let period_id = conn.last_insert_id() as u32;
+
+    record_audit(
+        &mut conn,
+        coordinator.bn_number.inner(),
+        "exam_period_created",
+        &period_id.to_string(),
+        &format!("{} {} {} to {}", name.trim(), period_type.as_str(), start_date, end_date),
+    )?;
+
+    Ok(period_id)
+}
+
+#[tauri::command]
+fn update_exam_period(
+    period_id: u32,
+    name: String,
+    period_type: ExamPeriodType,
+    start_date: String,
+    end_date: String,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<(), String> {
+    let coordinator = require_role(&current_user, &[EXAM_COORDINATOR])?;
+    validate_period_dates(&start_date, &end_date)?;
+
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    if load_exam_period(&mut conn, period_id)?.locked {
+        return Err(format!("Exam period {} is locked", period_id));
+    }
+    ensure_no_period_overlap(&mut conn, Some(period_id), &start_date, &end_date)?;
+
+    let outside: Option<u64> = conn.exec_first(
+        r"SELECT COUNT(*) FROM exam_transaction
+        WHERE period_id = :period_id AND (transaction_date < :start_date OR transaction_date > :end_date)",
+        params! { "period_id" => period_id, "start_date" => &start_date, "end_date" => &end_date },
+    ).map_err(|e| format!("Failed to execute query: {}", e))?;
+    let outside = outside.unwrap_or(0);
+    if outside > 0 {
+        return Err(format!("{} exams of this period fall outside {} to {}", outside, start_date, end_date));
+    }
+
+    conn.exec_drop(
+        r"UPDATE exam_period SET name = :name, period_type = :period_type, start_date = :start_date, end_date = :end_date
+        WHERE period_id = :period_id",
+        params! {
+            "period_id" => period_id,
+            "name" => name.trim(),
+            "period_type" => period_type.as_str(),
+            "start_date" => &start_date,
+            "end_date" => &end_date,
+        },
+    ).map_err(|e| format!("Failed to update exam period: {}", e))?;
+
+    record_audit(
+        &mut conn,
+        coordinator.bn_number.inner(),
+        "exam_period_updated",
+        &period_id.to_string(),
+        &format!("{} {} {} to {}", name.trim(), period_type.as_str(), start_date, end_date),
+    )
+}
+
+/// A locked period rejects new, changed and re-seated exams until it is unlocked again.
+#[tauri::command]
+fn set_exam_period_locked(
+    period_id: u32,
+    locked: bool,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<(), String> {
+    let coordinator = require_role(&current_user, &[EXAM_COORDINATOR])?;
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    if !locked && load_exam_period(&mut conn, period_id)?.archived {
+        return Err(format!("Exam period {} is archived and cannot be unlocked", period_id));
+    }
+
+    conn.exec_drop(
+        "UPDATE exam_period SET locked = :locked WHERE period_id = :period_id",
+        params! { "period_id" => period_id, "locked" => locked },
+    ).map_err(|e| format!("Failed to update exam period: {}", e))?;
+
+    record_audit(
+        &mut conn,
+        coordinator.bn_number.inner(),
+        if locked { "exam_period_locked" } else { "exam_period_unlocked" },
+        &period_id.to_string(),
+        "",
+    )
+}
+
+/// Archiving also locks the period and hides its exams from default listings and reports.
+#[tauri::command]
+fn archive_exam_period(
+    period_id: u32,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<(), String> {
+    let coordinator = require_role(&current_user, &[EXAM_COORDINATOR])?;
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    let finished: Option<bool> = conn.exec_first(
+        "SELECT end_date < CURDATE() FROM exam_period WHERE period_id = :period_id",
+        params! { "period_id" => period_id },
+    ).map_err(|e| format!("Failed to execute query: {}", e))?;
+    match finished {
+        None => return Err(format!("Exam period {} not found", period_id)),
+        Some(false) => return Err(format!("Exam period {} has not finished yet", period_id)),
+        Some(true) => {}
+    }
+
+    conn.exec_drop(
+        "UPDATE exam_period SET locked = TRUE, archived = TRUE WHERE period_id = :period_id",
+        params! { "period_id" => period_id },
+    ).map_err(|e| format!("Failed to archive exam period: {}", e))?;
+
+    record_audit(&mut conn, coordinator.bn_number.inner(), "exam_period_archived", &period_id.to_string(), "")
+}
+
+// Exams are never scheduled on these weekdays, independent of the blackout table.
+const CLOSED_WEEKDAYS: [chrono::Weekday; 1] = [chrono::Weekday::Sun];
+
+#[derive(Debug, Serialize)]
+pub struct BlackoutDate {
+    pub blackout_id: u32,
+    pub blackout_date: String,
+    // None closes every campus.
+    pub campus: Option<String>,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BlackoutImportReport {
+    pub added: usize,
+    pub skipped: usize,
+}
+
+fn load_blackout_dates(conn: &mut PooledConn, date_from: Option<&str>, date_to: Option<&str>) -> Result<Vec<BlackoutDate>, String> {
+    conn.exec_map(
+        r"SELECT blackout_id, DATE_FORMAT(blackout_date, '%Y-%m-%d'), campus, reason FROM blackout_date
+        WHERE (:date_from IS NULL OR blackout_date >= :date_from)
+          AND (:date_to IS NULL OR blackout_date <= :date_to)
+        ORDER BY blackout_date, campus",
+        params! { "date_from" => date_from, "date_to" => date_to },
+        |(blackout_id, blackout_date, campus, reason)| BlackoutDate { blackout_id, blackout_date, campus, reason },
+    ).map_err(|e| format!("Failed to fetch blackout dates: {}", e))
+}
+
+// Why `date` is closed for exams on `campus`, if it is.
+fn blackout_reason(date: chrono::NaiveDate, campus: Option<&str>, blackouts: &[BlackoutDate]) -> Option<String> {
+    if CLOSED_WEEKDAYS.contains(&date.weekday()) {
+        return Some(format!("no exams on {}", date.format("%A")));
+    }
+
+    let date = date.format("%Y-%m-%d").to_string();
+    blackouts.iter()
+        .find(|blackout| {
+            blackout.blackout_date == date
+                && (blackout.campus.is_none() || blackout.campus.as_deref() == campus)
+        })
+        .map(|blackout| blackout.reason.clone())
+}
+
+fn ensure_date_schedulable(conn: &mut PooledConn, transaction_date: &str, room_number: &str) -> Result<(), String> {
+    let date = chrono::NaiveDate::parse_from_str(transaction_date, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date '{}', expected YYYY-MM-DD", transaction_date))?;
+
+    let campus: Option<String> = conn.exec_first(
+        "SELECT campus FROM room WHERE room_number = :room_number",
+        params! { "room_number" => room_number },
+    ).map_err(|e| format!("Failed to execute query: {}", e))?;
+    let blackouts = load_blackout_dates(conn, Some(transaction_date), Some(transaction_date))?;
+
+    match blackout_reason(date, campus.as_deref(), &blackouts) {
+        Some(reason) => Err(format!("{} is closed for exams: {}", transaction_date, reason)),
+        None => Ok(()),
+    }
+}
+
+fn unescape_ics_text(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => unescaped.push('\n'),
+            Some(other) => unescaped.push(other),
+            None => {}
+        }
+    }
+    unescaped
+}
+
+// The date part of a DTSTART/DTEND value, and whether the value was a plain DATE.
+fn parse_ics_date(value: &str) -> Option<(chrono::NaiveDate, bool)> {
+    let date = chrono::NaiveDate::parse_from_str(value.get(..8)?, "%Y%m%d").ok()?;
+    Some((date, value.len() == 8))
+}
+
+#[derive(Default)]
+struct IcsEvent {
+    start: Option<(chrono::NaiveDate, bool)>,
+    end: Option<(chrono::NaiveDate, bool)>,
+    summary: String,
+}
+
+// Every (date, summary) covered by the VEVENTs of an iCalendar file. All-day DTEND is exclusive.
+fn parse_ics_closures(contents: &str) -> Vec<(chrono::NaiveDate, String)> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in contents.lines() {
+        match (line.strip_prefix(' ').or_else(|| line.strip_prefix('\t')), lines.last_mut()) {
+            (Some(continuation), Some(previous)) => previous.push_str(continuation),
+            _ => lines.push(line.to_string()),
+        }
+    }
+
+    let mut closures = Vec::new();
+    let mut event: Option<IcsEvent> = None;
+    for line in &lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let property = name.split(';').next().unwrap_or_default().to_uppercase();
+        let value = value.trim();
+
+        match property.as_str() {
+            "BEGIN" if value == "VEVENT" => event = Some(IcsEvent::default()),
+            "END" if value == "VEVENT" => {
+                let Some(IcsEvent { start: Some((start, _)), end, summary }) = event.take() else {
+                    continue;
+                };
+                let last = match end {
+                    Some((end, true)) if end > start => end.pred_opt().unwrap_or(start),
+                    Some((end, false)) if end > start => end,
+                    _ => start,
+                };
+                let mut date = start;
+                while date <= last {
+                    closures.push((date, summary.clone()));
+                    match date.succ_opt() {
+                        Some(next) => date = next,
+                        None => break,
+                    }
+                }
+            }
+            _ => {
+                let Some(event) = event.as_mut() else {
+                    continue;
+                };
+                match property.as_str() {
+                    "DTSTART" => event.start = parse_ics_date(value),
+                    "DTEND" => event.end = parse_ics_date(value),
+                    "SUMMARY" => event.summary = unescape_ics_text(value),
+                    _ => {}
+                }
+            }
+        }
+    }
+    closures
+}
+
+#[tauri::command]
+fn get_blackout_dates(
+    date_from: Option<String>,
+    date_to: Option<String>,
+    mysql_pool: State<'_, Pool>,
+) -> Result<Vec<BlackoutDate>, String> {
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+    load_blackout_dates(&mut conn, date_from.as_deref(), date_to.as_deref())
+}
+
+#[tauri::command]
+fn add_blackout_date(
+    blackout_date: String,
+    campus: Option<String>,
+    reason: String,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<u32, String> {
+    let coordinator = require_role(&current_user, &[EXAM_COORDINATOR])?;
+    chrono::NaiveDate::parse_from_str(&blackout_date, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date '{}', expected YYYY-MM-DD", blackout_date))?;
+    let campus = campus.filter(|campus| !campus.trim().is_empty());
+
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    conn.exec_drop(
+        "INSERT INTO blackout_date (blackout_date, campus, reason) VALUES (:blackout_date, :campus, :reason)",
+        params! { "blackout_date" => &blackout_date, "campus" => &campus, "reason" => reason.trim() },
+    ).map_err(|e| format!("Failed to add blackout date: {}", e))?;
+    let blackout_id = conn.last_insert_id() as u32;
+
+    record_audit(
+        &mut conn,
+        coordinator.bn_number.inner(),
+        "blackout_date_added",
+        &blackout_date,
+        &format!("{} {}", campus.as_deref().unwrap_or("all campuses"), reason.trim()),
+    )?;
+
+    Ok(blackout_id)
+}
+
+#[tauri::command]
+fn remove_blackout_date(
+    blackout_id: u32,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<(), String> {
+    let coordinator = require_role(&current_user, &[EXAM_COORDINATOR])?;
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    conn.exec_drop(
+        "DELETE FROM blackout_date WHERE blackout_id = :blackout_id",
+        params! { "blackout_id" => blackout_id },
+    ).map_err(|e| format!("Failed to remove blackout date: {}", e))?;
+
+    if conn.affected_rows() == 0 {
+        return Err(format!("Blackout date {} not found", blackout_id));
+    }
+
+    record_audit(&mut conn, coordinator.bn_number.inner(), "blackout_date_removed", &blackout_id.to_string(), "")
+}
+
+/// Adds every day covered by the events of an .ics holiday calendar as a blackout date, for one
+/// campus or all of them. Days already blocked for that campus are skipped.
+#[tauri::command]
+async fn import_blackout_ics(
+    path: Option<String>,
+    campus: Option<String>,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<Option<BlackoutImportReport>, String> {
+    let coordinator = require_role(&current_user, &[EXAM_COORDINATOR])?;
+    let campus = campus.filter(|campus| !campus.trim().is_empty());
+
+    let path = match path {
+        Some(path) => std::path::PathBuf::from(path),
+        None => {
+            let Some(path) = FileDialogBuilder::new()
+                .add_filter("iCalendar", &["ics"])
+                .pick_file()
+            else {
+                return Ok(None);
+            };
+            path
+        }
+    };
+
+    let contents = std::fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let closures = parse_ics_closures(&contents);
+
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+    let mut transaction = conn.start_transaction(TxOpts::default())
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let mut report = BlackoutImportReport { added: 0, skipped: 0 };
+    for (date, summary) in closures {
+        let date = date.format("%Y-%m-%d").to_string();
+        let exists: Option<u32> = transaction.exec_first(
+            "SELECT blackout_id FROM blackout_date WHERE blackout_date = :blackout_date AND campus <=> :campus",
+            params! { "blackout_date" => &date, "campus" => &campus },
+        ).map_err(|e| format!("Failed to execute query: {}", e))?;
+        if exists.is_some() {
+            report.skipped += 1;
+            continue;
+        }
+
+        transaction.exec_drop(
+            "INSERT INTO blackout_date (blackout_date, campus, reason) VALUES (:blackout_date, :campus, :reason)",
+            params! { "blackout_date" => &date, "campus" => &campus, "reason" => summary.chars().take(255).collect::<String>() },
+        ).map_err(|e| format!("Failed to add blackout date: {}", e))?;
+        report.added += 1;
+    }
+
+    record_audit(
+        &mut transaction,
+        coordinator.bn_number.inner(),
+        "blackout_dates_imported",
+        campus.as_deref().unwrap_or("all campuses"),
+        &format!("{} added, {} skipped from {}", report.added, report.skipped, path.display()),
+    )?;
+
+    transaction.commit()
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    Ok(Some(report))
+}
+
+// Seats actually usable in a room: broken seats are taken out, and labs with a known computer count
+// cannot seat more students than they have computers. Expects room LEFT JOIN room_attribute.
+const EFFECTIVE_CAPACITY_SQL: &str = "LEAST(
+    GREATEST(CAST(room.room_capacity AS SIGNED) - COALESCE(room_attribute.broken_seats, 0), 0),
+    COALESCE(room_attribute.computer_count, room.room_capacity)
+)";
+
+#[derive(Debug, Serialize)]
+pub struct RoomDetails {
+    pub room_number: String,
+    pub campus: String,
+    pub room_capacity: i32,
+    pub effective_capacity: u32,
+    pub computer_count: Option<u32>,
+    pub broken_seats: u32,
+    pub accessible: bool,
+    pub has_projector: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RoomAttributes {
+    pub computer_count: Option<u32>,
+    pub broken_seats: u32,
+    pub accessible: bool,
+    pub has_projector: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RoomUnavailability {
+    pub unavailability_id: u32,
+    pub room_number: String,
+    pub start_at: String,
+    pub end_at: String,
+    pub reason: String,
+}
+
+fn load_room_unavailability(
+    conn: &mut PooledConn,
+    room_number: Option<&str>,
+    date_from: Option<&str>,
+) -> Result<Vec<RoomUnavailability>, String> {
+    conn.exec_map(
+        r"SELECT unavailability_id, room_number, DATE_FORMAT(start_at, '%Y-%m-%d %H:%i:%s'),
+            DATE_FORMAT(end_at, '%Y-%m-%d %H:%i:%s'), reason
+        FROM room_unavailability
+        WHERE (:room_number IS NULL OR room_number = :room_number)
+          AND (:date_from IS NULL OR end_at >= :date_from)
+        ORDER BY start_at",
+        params! { "room_number" => room_number, "date_from" => date_from },
+        |(unavailability_id, room_number, start_at, end_at, reason)| RoomUnavailability {
+            unavailability_id,
+            room_number,
+            start_at,
+            end_at,
+            reason,
+        },
+    ).map_err(|e| format!("Failed to fetch room unavailability: {}", e))
+}
+
+// The maintenance window overlapping `room_number` between `start_at` and `end_at`, if any.
+// Timestamps are "YYYY-MM-DD HH:MM:SS", so they compare correctly as strings.
+fn unavailability_overlapping<'a>(
+    windows: &'a [RoomUnavailability],
+    room_number: &str,
+    start_at: &str,
+    end_at: &str,
+) -> Option<&'a RoomUnavailability> {
+    windows.iter().find(|window| {
+        window.room_number == room_number && window.start_at.as_str() < end_at && start_at < window.end_at.as_str()
+    })
+}
+
+fn ensure_room_available(conn: &mut PooledConn, room_number: &str, transaction_date: &str, shift_id: &str) -> Result<(), String> {
+    let shift: Option<(String, String)> = conn.exec_first(
+        r"SELECT TIME_FORMAT(start_time, '%H:%i:%s'), TIME_FORMAT(end_time, '%H:%i:%s') FROM shift
+        WHERE shift_id = :shift_id AND retired = FALSE",
+        params! { "shift_id" => shift_id },
+    ).map_err(|e| format!("Failed to execute query: {}", e))?;
+    let Some((start_time, end_time)) = shift else {
+        return Err(format!("Unknown or retired shift {}", shift_id));
+    };
+
+    let start_at = format!("{} {}", transaction_date, start_time);
+    let end_at = format!("{} {}", transaction_date, end_time);
+    let windows = load_room_unavailability(conn, Some(room_number), Some(transaction_date))?;
+
+    match unavailability_overlapping(&windows, room_number, &start_at, &end_at) {
+        Some(window) => Err(format!(
+            "Room {} is unavailable from {} to {}: {}",
+            room_number, window.start_at, window.end_at, window.reason
+        )),
+        None => Ok(()),
+    }
+}
+
+#[tauri::command]
+fn get_room_details(room_number: Option<String>, mysql_pool: State<'_, Pool>) -> Result<Vec<RoomDetails>, String> {
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    conn.exec_map(
+        format!(
+            r"SELECT room.room_number, room.campus, room.room_capacity, {},
+                room_attribute.computer_count, COALESCE(room_attribute.broken_seats, 0),
+                COALESCE(room_attribute.accessible, FALSE), COALESCE(room_attribute.has_projector, FALSE)
+            FROM room
+            LEFT JOIN room_attribute ON room_attribute.room_number = room.room_number
+            WHERE :room_number IS NULL OR room.room_number = :room_number
+            ORDER BY room.room_number",
+            EFFECTIVE_CAPACITY_SQL
+        ),
+        params! { "room_number" => &room_number },
+        |(room_number, campus, room_capacity, effective_capacity, computer_count, broken_seats, accessible, has_projector)| RoomDetails {
+            room_number,
+            campus,
+            room_capacity,
+            effective_capacity,
+            computer_count,
+            broken_seats,
+            accessible,
+            has_projector,
+        },
+    ).map_err(|e| format!("Failed to fetch rooms: {}", e))
+}
+
+/// Room attributes live in their own table, so a master-data sync never overwrites them.
+#[tauri::command]
+fn update_room_attributes(
+    room_number: String,
+    attributes: RoomAttributes,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<(), String> {
+    let coordinator = require_role(&current_user, &[EXAM_COORDINATOR])?;
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    let capacity: Option<i32> = conn.exec_first(
+        "SELECT room_capacity FROM room WHERE room_number = :room_number",
+        params! { "room_number" => &room_number },
+    ).map_err(|e| format!("Failed to execute query: {}", e))?;
+    let Some(capacity) = capacity else {
+        return Err(format!("Room {} not found", room_number));
+    };
+    if attributes.broken_seats as i64 > capacity as i64 {
+        return Err(format!("Room {} only has {} seats", room_number, capacity));
+    }
+
+    conn.exec_drop(
+        r"INSERT INTO room_attribute (room_number, computer_count, broken_seats, accessible, has_projector, updated_by)
+        VALUES (:room_number, :computer_count, :broken_seats, :accessible, :has_projector, :updated_by)
+        ON DUPLICATE KEY UPDATE computer_count = VALUES(computer_count), broken_seats = VALUES(broken_seats),
+            accessible = VALUES(accessible), has_projector = VALUES(has_projector), updated_by = VALUES(updated_by)",
+        params! {
+            "room_number" => &room_number,
+            "computer_count" => attributes.computer_count,
+            "broken_seats" => attributes.broken_seats,
+            "accessible" => attributes.accessible,
+            "has_projector" => attributes.has_projector,
+            "updated_by" => coordinator.bn_number.inner(),
+        },
+    ).map_err(|e| format!("Failed to update room attributes: {}", e))?;
+
+    record_audit(
+        &mut conn,
+        coordinator.bn_number.inner(),
+        "room_attributes_updated",
+        &room_number,
+        &format!("{:?}", attributes),
+    )
+}
+
+#[tauri::command]
+fn get_room_unavailability(
+    room_number: Option<String>,
+    date_from: Option<String>,
+    mysql_pool: State<'_, Pool>,
+) -> Result<Vec<RoomUnavailability>, String> {
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+    load_room_unavailability(&mut conn, room_number.as_deref(), date_from.as_deref())
+}
+
+/// Blocks a room for maintenance between two "YYYY-MM-DD HH:MM" timestamps. Exams already booked
+/// inside the window are reported back instead of being moved.
+#[tauri::command]
+fn add_room_unavailability(
+    room_number: String,
+    start_at: String,
+    end_at: String,
+    reason: String,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    let coordinator = require_role(&current_user, &[EXAM_COORDINATOR])?;
+
+    let parse = |value: &str| {
+        chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+            .or_else(|_| chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M"))
+            .map_err(|_| format!("Invalid timestamp '{}', expected YYYY-MM-DD HH:MM", value))
+    };
+    let (start, end) = (parse(&start_at)?, parse(&end_at)?);
+    if start >= end {
+        return Err("Unavailability must start before it ends".to_string());
+    }
+
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    conn.exec_drop(
+        r"INSERT INTO room_unavailability (room_number, start_at, end_at, reason, created_by)
+        VALUES (:room_number, :start_at, :end_at, :reason, :created_by)",
+        params! {
+            "room_number" => &room_number,
+            "start_at" => start.format("%Y-%m-%d %H:%M:%S").to_string(),
+            "end_at" => end.format("%Y-%m-%d %H:%M:%S").to_string(),
+            "reason" => reason.trim(),
+            "created_by" => coordinator.bn_number.inner(),
+        },
+    ).map_err(|e| format!("Failed to add room unavailability: {}", e))?;
+
+    let affected: Vec<String> = conn.exec(
+        r"SELECT et.transaction_id FROM exam_transaction et
+        JOIN shift ON shift.shift_id = et.shift_id
+        WHERE et.room_number = :room_number
+          AND TIMESTAMP(et.transaction_date, shift.start_time) < :end_at
+          AND :start_at < TIMESTAMP(et.transaction_date, shift.end_time)
+        ORDER BY et.transaction_date, shift.start_time",
+        params! {
+            "room_number" => &room_number,
+            "start_at" => start.format("%Y-%m-%d %H:%M:%S").to_string(),
+            "end_at" => end.format("%Y-%m-%d %H:%M:%S").to_string(),
+        },
+    ).map_err(|e| format!("Failed to check booked exams: {}", e))?;
+
+    record_audit(
+        &mut conn,
+        coordinator.bn_number.inner(),
+        "room_unavailability_added",
+        &room_number,
+        &format!("{} to {} {}", start_at, end_at, reason.trim()),
+    )?;
+
+    Ok(affected)
+}
+
+#[tauri::command]
+fn remove_room_unavailability(
+    unavailability_id: u32,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<(), String> {
+    let coordinator = require_role(&current_user, &[EXAM_COORDINATOR])?;
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    conn.exec_drop(
+        "DELETE FROM room_unavailability WHERE unavailability_id = :unavailability_id",
+        params! { "unavailability_id" => unavailability_id },
+    ).map_err(|e| format!("Failed to remove room unavailability: {}", e))?;
+
+    if conn.affected_rows() == 0 {
+        return Err(format!("Room unavailability {} not found", unavailability_id));
+    }
+
+    record_audit(&mut conn, coordinator.bn_number.inner(), "room_unavailability_removed", &unavailability_id.to_string(), "")
+}
+
+// Minutes a proctor needs between campuses when no travel time has been configured for the pair.
+const DEFAULT_CAMPUS_TRAVEL_MINUTES: u32 = 60;
+
+// bn_number of the proctor of `et`, whether the row stores the bn_number or the initial.
+const PROCTOR_BN_SQL: &str = "COALESCE(
+    (SELECT users.bn_number FROM users WHERE users.bn_number = et.proctor OR users.initial = et.proctor LIMIT 1),
+    et.proctor
+)";
+
+#[derive(Debug, Serialize)]
+pub struct CampusTravelTime {
+    pub from_campus: String,
+    pub to_campus: String,
+    pub minutes: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TravelWarning {
+    pub proctor: String,
+    pub date: String,
+    // Transaction id, or "row N" for rows of an import file.
+    pub from_exam: String,
+    pub to_exam: String,
+    pub from_campus: String,
+    pub to_campus: String,
+    pub gap_minutes: i64,
+    pub required_minutes: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RoomSuggestion {
+    pub room_number: String,
+    pub campus: String,
+    pub effective_capacity: u32,
+    // The subject already has exams on this campus in the same exam period.
+    pub preferred: bool,
+}
+
+struct ProctorSlot {
+    proctor: String,
+    exam: String,
+    date: String,
+    start_time: String,
+    end_time: String,
+    campus: String,
+}
+
+// Travel times are symmetric, so pairs are stored with the campuses in sorted order.
+fn campus_pair(a: &str, b: &str) -> (String, String) {
+    if a <= b {
+        (a.to_string(), b.to_string())
+    } else {
+        (b.to_string(), a.to_string())
+    }
+}
+
+fn load_travel_times(conn: &mut PooledConn) -> Result<HashMap<(String, String), u32>, String> {
+    conn.query_map(
+        "SELECT campus_a, campus_b, minutes FROM campus_travel",
+        |(campus_a, campus_b, minutes): (String, String, u32)| ((campus_a, campus_b), minutes),
+    ).map(|pairs| pairs.into_iter().collect())
+        .map_err(|e| format!("Failed to fetch campus travel times: {}", e))
+}
+
+fn load_proctor_slots(conn: &mut PooledConn, condition: &str, params: mysql::Params) -> Result<Vec<ProctorSlot>, String> {
+    conn.exec_map(
+        format!(
+            r"SELECT {}, et.transaction_id, DATE_FORMAT(et.transaction_date, '%Y-%m-%d'),
+                TIME_FORMAT(shift.start_time, '%H:%i:%s'), TIME_FORMAT(shift.end_time, '%H:%i:%s'), room.campus
+            FROM exam_transaction et
+            JOIN shift ON shift.shift_id = et.shift_id
+            JOIN room ON room.room_number = et.room_number
+            WHERE et.proctor IS NOT NULL AND {}",
+            PROCTOR_BN_SQL, condition
+        ),
+        params,
+        |(proctor, exam, date, start_time, end_time, campus)| ProctorSlot { proctor, exam, date, start_time, end_time, campus },
+    ).map_err(|e| format!("Failed to fetch proctor assignments: {}", e))
+}
+
+// Consecutive duties of one proctor on one day that switch campus with less time in between than
+// the trip takes.
+fn travel_warnings(mut slots: Vec<ProctorSlot>, travel_times: &HashMap<(String, String), u32>) -> Vec<TravelWarning> {
+    slots.sort_by(|a, b| (&a.proctor, &a.date, &a.start_time).cmp(&(&b.proctor, &b.date, &b.start_time)));
+
+    let parse = |time: &str| chrono::NaiveTime::parse_from_str(time, "%H:%M:%S").ok();
+    slots.windows(2)
+        .filter_map(|pair| {
+            let (previous, next) = (&pair[0], &pair[1]);
+            if previous.proctor != next.proctor || previous.date != next.date || previous.campus == next.campus {
+                return None;
+            }
+            let gap_minutes = (parse(&next.start_time)? - parse(&previous.end_time)?).num_minutes();
+            let required_minutes = travel_times
+                .get(&campus_pair(&previous.campus, &next.campus))
+                .copied()
+                .unwrap_or(DEFAULT_CAMPUS_TRAVEL_MINUTES);
+
+            (gap_minutes < required_minutes as i64).then(|| TravelWarning {
+                proctor: next.proctor.clone(),
+                date: next.date.clone(),
+                from_exam: previous.exam.clone(),
+                to_exam: next.exam.clone(),
+                from_campus: previous.campus.clone(),
+                to_campus: next.campus.clone(),
+                gap_minutes,
+                required_minutes,
+            })
+        })
+        .collect()
+}
+
+#[tauri::command]
+fn get_campus_travel_times(mysql_pool: State<'_, Pool>) -> Result<Vec<CampusTravelTime>, String> {
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    conn.query_map(
+        "SELECT campus_a, campus_b, minutes FROM campus_travel ORDER BY campus_a, campus_b",
+        |(from_campus, to_campus, minutes)| CampusTravelTime { from_campus, to_campus, minutes },
+    ).map_err(|e| format!("Failed to fetch campus travel times: {}", e))
+}
+
+#[tauri::command]
+fn set_campus_travel_time(
+    from_campus: String,
+    to_campus: String,
+    minutes: u32,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<(), String> {
+    let coordinator = require_role(&current_user, &[EXAM_COORDINATOR])?;
+    if from_campus == to_campus {
+        return Err("Travel time needs two different campuses".to_string());
+    }
+    let (campus_a, campus_b) = campus_pair(&from_campus, &to_campus);
+
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    conn.exec_drop(
+        r"INSERT INTO campus_travel (campus_a, campus_b, minutes) VALUES (:campus_a, :campus_b, :minutes)
+        ON DUPLICATE KEY UPDATE minutes = VALUES(minutes)",
+        params! { "campus_a" => &campus_a, "campus_b" => &campus_b, "minutes" => minutes },
+    ).map_err(|e| format!("Failed to set campus travel time: {}", e))?;
+
+    record_audit(
+        &mut conn,
+        coordinator.bn_number.inner(),
+        "campus_travel_time_set",
+        &format!("{} - {}", campus_a, campus_b),
+        &format!("{} minutes", minutes),
+    )
+}
+
+/// Rooms that are free for an exam in the given slot: not booked, not under maintenance and not on a
+/// closed campus. With a `subject_code`, campuses already hosting that subject in the same exam
+/// period come first so a subject's classes stay together.
+#[tauri::command]
+fn get_available_rooms(
+    transaction_date: String,
+    shift_id: String,
+    campus: Option<String>,
+    subject_code: Option<String>,
+    mysql_pool: State<'_, Pool>,
+) -> Result<Vec<RoomSuggestion>, String> {
+    let date = chrono::NaiveDate::parse_from_str(&transaction_date, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date '{}', expected YYYY-MM-DD", transaction_date))?;
+
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    let shift: Option<(String, String)> = conn.exec_first(
+        "SELECT TIME_FORMAT(start_time, '%H:%i:%s'), TIME_FORMAT(end_time, '%H:%i:%s') FROM shift WHERE shift_id = :shift_id AND retired = FALSE",
+        params! { "shift_id" => &shift_id },
+    ).map_err(|e| format!("Failed to execute query: {}", e))?;
+    let Some((start_time, end_time)) = shift else {
+        return Err(format!("Unknown shift {}", shift_id));
+    };
+
+    let candidates: Vec<(String, String, u32, bool)> = conn.exec(
+        format!(
+            r"SELECT room.room_number, room.campus, {},
+                EXISTS (
+                    SELECT 1 FROM exam_transaction et
+                    JOIN room other_room ON other_room.room_number = et.room_number
+                    JOIN exam_period ON exam_period.period_id = et.period_id
+                    WHERE et.subject_code = :subject_code AND other_room.campus = room.campus
+                      AND :transaction_date BETWEEN exam_period.start_date AND exam_period.end_date
+                )
+            FROM room
+            LEFT JOIN room_attribute ON room_attribute.room_number = room.room_number
+            WHERE (:campus IS NULL OR room.campus = :campus)
+              AND NOT EXISTS (
+                  SELECT 1 FROM exam_transaction booked
+                  WHERE booked.room_number = room.room_number
+                    AND booked.transaction_date = :transaction_date AND booked.shift_id = :shift_id
+              )",
+            EFFECTIVE_CAPACITY_SQL
+        ),
+        params! {
+            "campus" => &campus,
+            "subject_code" => &subject_code,
+            "transaction_date" => &transaction_date,
+            "shift_id" => &shift_id,
+        },
+    ).map_err(|e| format!("Failed to fetch rooms: {}", e))?;
+
+    let blackouts = load_blackout_dates(&mut conn, Some(&transaction_date), Some(&transaction_date))?;
+    let unavailability = load_room_unavailability(&mut conn, None, Some(&transaction_date))?;
+    let start_at = format!("{} {}", transaction_date, start_time);
+    let end_at = format!("{} {}", transaction_date, end_time);
+
+    let mut rooms: Vec<RoomSuggestion> = candidates.into_iter()
+        .filter(|(room_number, campus, _, _)| {
+            blackout_reason(date, Some(campus.as_str()), &blackouts).is_none()
+                && unavailability_overlapping(&unavailability, room_number, &start_at, &end_at).is_none()
+        })
+        .map(|(room_number, campus, effective_capacity, preferred)| RoomSuggestion {
+            room_number,
+            campus,
+            effective_capacity,
+            preferred,
+        })
+        .collect();
+
+    rooms.sort_by(|a, b| {
+        b.preferred.cmp(&a.preferred)
+            .then_with(|| b.effective_capacity.cmp(&a.effective_capacity))
+            .then_with(|| a.room_number.cmp(&b.room_number))
+    });
+    Ok(rooms)
+}
+
+// WHERE conditions and their named parameters, built up from optional filter fields.
+struct Filters {
+    conditions: Vec<String>,
+    params: Vec<(String, mysql::Value)>,
+}
+
+impl Filters {
+    fn new() -> Self {
+        Self {
+            conditions: Vec::new(),
+            params: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, condition: &str, name: &str, value: impl Into<mysql::Value>) {
+        self.conditions.push(condition.to_string());
+        self.params.push((name.to_string(), value.into()));
+    }
+
+    fn require(&mut self, condition: &str) {
+        self.conditions.push(condition.to_string());
+    }
+
+    fn where_clause(&self) -> String {
+        if self.conditions.is_empty() {
+            String::new()
+        } else {
+            format!(" WHERE {}", self.conditions.join(" AND "))
+        }
+    }
+
+    fn params(&self) -> mysql::Params {
+        if self.params.is_empty() {
+            mysql::Params::Empty
+        } else {
+            mysql::Params::from(self.params.clone())
+        }
+    }
+}
+
+struct ListQuery<'a> {
+    from: &'a str,
+    columns: &'a str,
+    key_column: &'a str,
+    // (sort key accepted from the UI, non-null SQL columns ordered on in turn); the first entry is the default.
+    sort_keys: &'a [(&'a str, &'a [&'a str])],
+}
+
+// Keyset pagination: the cursor carries the sort values and key of the last row of the previous page.
+// Rows are ordered on the typed columns themselves with the key as tiebreaker, so dates and numbers
+// sort by value rather than as text.
+fn fetch_page<T>(
+    conn: &mut PooledConn,
+    query: &ListQuery,
+    mut filters: Filters,
+    page: PageRequest,
+    map_row: impl Fn(&mut mysql::Row) -> Result<T, String>,
+) -> Result<Page<T>, String> {
+    let sort_columns = match page.sort_by.as_deref() {
+        None => query.sort_keys[0].1,
+        Some(sort_by) => query.sort_keys
+            .iter()
+            .find(|(key, _)| *key == sort_by)
+            .map(|(_, columns)| *columns)
+            .ok_or_else(|| format!("Cannot sort by {}", sort_by))?,
+    };
+    let limit = page.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+
+    let total: Option<u64> = conn.exec_first(
+        format!("SELECT COUNT(*) FROM {}{}", query.from, filters.where_clause()),
+        filters.params(),
+    ).map_err(|e| format!("Failed to count rows: {}", e))?;
+
+    let order_columns: Vec<&str> = sort_columns.iter().copied().chain(std::iter::once(query.key_column)).collect();
+
+    if let Some(cursor) = page.cursor {
+        let cursor: PageCursor = serde_json::from_str(&cursor).map_err(|e| format!("Invalid cursor: {}", e))?;
+        if cursor.values.len() != sort_columns.len() {
+            return Err("Invalid cursor: it was issued for a different sort".to_string());
+        }
+        let placeholders: Vec<String> = (0..sort_columns.len())
+            .map(|index| format!(":cursor_value_{}", index))
+            .chain(std::iter::once(":cursor_key".to_string()))
+            .collect();
+        filters.conditions.push(format!(
+            "({}) {} ({})",
+            order_columns.join(", "),
+            if page.descending { "<" } else { ">" },
+            placeholders.join(", "),
+        ));
+        for (index, value) in cursor.values.into_iter().enumerate() {
+            filters.params.push((format!("cursor_value_{}", index), value.into()));
+        }
+        filters.params.push(("cursor_key".to_string(), cursor.key.into()));
+    }
+    filters.params.push(("limit".to_string(), (limit + 1).into()));
+
+    let direction = if page.descending { "DESC" } else { "ASC" };
+    let cursor_columns: Vec<String> = sort_columns
+        .iter()
+        .enumerate()
+        .map(|(index, column)| format!("CAST({} AS CHAR) AS cursor_value_{}", column, index))
+        .collect();
+    let order_by: Vec<String> = order_columns.iter().map(|column| format!("{} {}", column, direction)).collect();
+    let mut rows: Vec<mysql::Row> = conn.exec(
+        format!(
+            "SELECT {columns}, {cursor_columns}, {key} AS cursor_key FROM {from}{where_clause}
+            ORDER BY {order_by} LIMIT :limit",
+            columns = query.columns,
+            cursor_columns = cursor_columns.join(", "),
+            key = query.key_column,
+            from = query.from,
+            where_clause = filters.where_clause(),
+            order_by = order_by.join(", "),
+        ),
+        filters.params(),
+    ).map_err(|e| format!("Failed to execute query: {}", e))?;
+
+    let has_more = rows.len() > limit as usize;
+    rows.truncate(limit as usize);
+
+    let next_cursor = match rows.last_mut() {
+        Some(last) if has_more => {
+            let values = (0..sort_columns.len())
+                .map(|index| take_column::<String>(last, &format!("cursor_value_{}", index)))
+                .collect::<Result<Vec<_>, _>>()?;
+            let key = take_column(last, "cursor_key")?;
+            Some(serde_json::to_string(&PageCursor { values, key }).map_err(|e| format!("Failed to encode cursor: {}", e))?)
+        }
+        _ => None,
+    };
+
+    Ok(Page {
+        items: rows.iter_mut().map(map_row).collect::<Result<_, _>>()?,
+        total: total.unwrap_or(0),
+        next_cursor,
+    })
+}
+
+fn take_column<T: mysql::prelude::FromValue>(row: &mut mysql::Row, column: &str) -> Result<T, String> {
+    row.take_opt(column)
+        .ok_or_else(|| format!("Column {} is missing from the result", column))?
+        .map_err(|e| format!("Failed to read column {}: {}", column, e))
+}
+
+#[tauri::command]
+fn list_users(filter: UserFilter, page: PageRequest, mysql_pool: State<'_, Pool>) -> Result<Page<User>, String> {
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    let mut filters = Filters::new();
+    if let Some(role) = filter.role {
+        filters.push(
+            &format!(
+                "(users.role = :role OR EXISTS (SELECT 1 FROM user_roles WHERE user_roles.bn_number = users.bn_number AND user_roles.role_name = :role AND {}))",
+                ACTIVE_GRANT_CONDITION
+            ),
+            "role",
+            role,
+        );
+    }
+    if let Some(major) = filter.major {
+        filters.push("users.major = :major", "major", major);
+    }
+
+    let query = ListQuery {
+        from: "users",
+        columns: "users.bn_number, users.nim, users.name, users.major, users.role, users.initial",
+        key_column: "users.bn_number",
+        sort_keys: &[
+            ("name", &["users.name"]),
+            ("nim", &["COALESCE(users.nim, '')"]),
+            ("initial", &["COALESCE(users.initial, '')"]),
+            ("major", &["COALESCE(users.major, '')"]),
+            ("role", &["COALESCE(users.role, '')"]),
+        ],
+    };
+
+    fetch_page(&mut conn, &query, filters, page, |row| {
+        Ok(User {
+            bn_number: take_column::<String>(row, "bn_number")?.into(),
+            nim: take_column::<Option<String>>(row, "nim")?.unwrap_or_default(),
+            name: take_column(row, "name")?,
+            major: take_column::<Option<String>>(row, "major")?.unwrap_or_default(),
+            role: take_column::<Option<String>>(row, "role")?.unwrap_or_default(),
+            initial: take_column(row, "initial")?,
+        })
+    })
+}
+
+#[tauri::command]
+fn list_rooms(filter: RoomFilter, page: PageRequest, mysql_pool: State<'_, Pool>) -> Result<Page<Room>, String> {
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    let mut filters = Filters::new();
+    if let Some(campus) = filter.campus {
+        filters.push("room.campus = :campus", "campus", campus);
+    }
+    if let Some(min_capacity) = filter.min_capacity {
+        filters.push("room.room_capacity >= :min_capacity", "min_capacity", min_capacity);
+    }
+
+    let query = ListQuery {
+        from: "room",
+        columns: "room.room_number, room.room_capacity, room.campus",
+        key_column: "room.room_number",
+        sort_keys: &[
+            ("room_number", &["room.room_number"]),
+            ("room_capacity", &["room.room_capacity"]),
+            ("campus", &["room.campus"]),
+        ],
+    };
+
+    fetch_page(&mut conn, &query, filters, page, |row| {
+        Ok(Room {
+            campus: take_column(row, "campus")?,
+            room_capacity: take_column(row, "room_capacity")?,
+            room_number_str: take_column(row, "room_number")?,
+        })
+    })
+}
+
+#[tauri::command]
+fn list_subjects(filter: SubjectFilter, page: PageRequest, mysql_pool: State<'_, Pool>) -> Result<Page<Subject>, String> {
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    let mut filters = Filters::new();
+    if let Some(name) = filter.name {
+        filters.push("subject.subject_name LIKE CONCAT('%', :name, '%')", "name", name);
+    }
+
+    let query = ListQuery {
+        from: "subject",
+        columns: "subject.subject_code, subject.subject_name",
+        key_column: "subject.subject_code",
+        sort_keys: &[
+            ("subject_code", &["subject.subject_code"]),
+            ("subject_name", &["subject.subject_name"]),
+        ],
+    };
+
+    fetch_page(&mut conn, &query, filters, page, |row| {
+        Ok(Subject {
+            subject_code_str: take_column(row, "subject_code")?,
+            subject_name: take_column(row, "subject_name")?,
+        })
+    })
+}
+
+// Expects exam_transaction joined with room under their table names.
+fn exam_transaction_filters(filter: ExamTransactionFilter) -> Filters {
+    let mut filters = Filters::new();
+    if let Some(date_from) = filter.date_from {
+        filters.push("exam_transaction.transaction_date >= :date_from", "date_from", date_from);
+    }
+    if let Some(date_to) = filter.date_to {
+        filters.push("exam_transaction.transaction_date <= :date_to", "date_to", date_to);
+    }
+    if let Some(status) = filter.status {
+        filters.push("exam_transaction.status = :status", "status", status);
+    }
+    if let Some(proctor) = filter.proctor {
+        filters.push("exam_transaction.proctor = :proctor", "proctor", proctor);
+    }
+    if let Some(subject_code) = filter.subject_code {
+        filters.push("exam_transaction.subject_code = :subject_code", "subject_code", subject_code);
+    }
+    if let Some(room_number) = filter.room_number {
+        filters.push("exam_transaction.room_number = :room_number", "room_number", room_number);
+    }
+    if let Some(campus) = filter.campus {
+        filters.push("room.campus = :campus", "campus", campus);
+    }
+    match filter.period_id {
+        Some(period_id) => filters.push("exam_transaction.period_id = :period_id", "period_id", period_id),
+        None => filters.require(
+            "NOT EXISTS (SELECT 1 FROM exam_period WHERE exam_period.period_id = exam_transaction.period_id AND exam_period.archived = TRUE)",
+        ),
+    }
+    filters
+}
+
+#[tauri::command]
+fn list_exam_transactions(
+    filter: ExamTransactionFilter,
+    page: PageRequest,
+    mysql_pool: State<'_, Pool>,
+) -> Result<Page<ExamTransaction>, String> {
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    let filters = exam_transaction_filters(filter);
+
+    let query = ListQuery {
+        from: r"exam_transaction JOIN room ON room.room_number = exam_transaction.room_number
+            JOIN shift ON shift.shift_id = exam_transaction.shift_id",
+        columns: r"exam_transaction.transaction_id, exam_transaction.subject_code, exam_transaction.room_number,
+            exam_transaction.shift_id, DATE_FORMAT(exam_transaction.transaction_date, '%Y-%m-%d') AS transaction_date,
+            exam_transaction.proctor, exam_transaction.status, exam_transaction.period_id, exam_transaction.makeup_of",
+        key_column: "exam_transaction.transaction_id",
+        sort_keys: &[
+            ("transaction_date", &["exam_transaction.transaction_date", "shift.start_time"]),
+            ("subject_code", &["exam_transaction.subject_code"]),
+            ("room_number", &["exam_transaction.room_number"]),
+            ("status", &["COALESCE(exam_transaction.status, '')"]),
+            ("proctor", &["COALESCE(exam_transaction.proctor, '')"]),
+        ],
+    };
+
+    fetch_page(&mut conn, &query, filters, page, |row| {
+        Ok(ExamTransaction {
+            transaction_id: take_column(row, "transaction_id")?,
+            subject_code: take_column(row, "subject_code")?,
+            room_number: take_column(row, "room_number")?,
+            shift_id: take_column(row, "shift_id")?,
+            transaction_date: take_column(row, "transaction_date")?,
+            proctor: take_column(row, "proctor")?,
+            status: take_column(row, "status")?,
+            period_id: take_column(row, "period_id")?,
+            makeup_of: take_column(row, "makeup_of")?,
+        })
+    })
+}
+
+// Exact matches rank above prefix matches, which rank above substring matches.
+fn match_score(column: &str) -> String {
+    format!(
+        "(CASE WHEN {c} = :term THEN 100 WHEN {c} LIKE :prefix THEN 60 WHEN {c} LIKE :contains THEN 30 ELSE 0 END)",
+        c = column
+    )
+}
+
+fn search_params(term: &str, limit: u32) -> mysql::Params {
+    let escaped = term.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+    params! {
+        "term" => term,
+        "prefix" => format!("{}%", escaped),
+        "contains" => format!("%{}%", escaped),
+        "limit" => limit,
+    }
+}
+
+fn search_users(conn: &mut PooledConn, term: &str, limit: u32) -> Result<Vec<SearchHit>, String> {
+    conn.exec_map(
+        format!(
+            r"SELECT bn_number, nim, name, initial, role, GREATEST({}, {}, {}) AS score
+            FROM users HAVING score > 0 ORDER BY score DESC, name LIMIT :limit",
+            match_score("COALESCE(nim, '')"),
+            match_score("name"),
+            match_score("COALESCE(initial, '')"),
+        ),
+        search_params(term, limit),
+        |(bn_number, nim, name, initial, role, score): (String, Option<String>, String, Option<String>, Option<String>, u32)| SearchHit::User {
+            bn_number,
+            nim,
+            name,
+            initial,
+            role,
+            score,
+        },
+    ).map_err(|e| format!("Failed to search users: {}", e))
+}
+
+fn search_subjects(conn: &mut PooledConn, term: &str, limit: u32) -> Result<Vec<SearchHit>, String> {
+    conn.exec_map(
+        format!(
+            r"SELECT subject_code, subject_name, GREATEST({}, {}) AS score
+            FROM subject HAVING score > 0 ORDER BY score DESC, subject_code LIMIT :limit",
+            match_score("subject_code"),
+            match_score("subject_name"),
+        ),
+        search_params(term, limit),
+        |(subject_code, subject_name, score)| SearchHit::Subject {
+            subject_code,
+            subject_name,
+            score,
+        },
+    ).map_err(|e| format!("Failed to search subjects: {}", e))
+}
+
+fn search_rooms(conn: &mut PooledConn, term: &str, limit: u32) -> Result<Vec<SearchHit>, String> {
+    conn.exec_map(
+        format!(
+            r"SELECT room_number, campus, room_capacity, GREATEST({}, {}) AS score
+            FROM room HAVING score > 0 ORDER BY score DESC, room_number LIMIT :limit",
+            match_score("room_number"),
+            match_score("campus"),
+        ),
+        search_params(term, limit),
+        |(room_number, campus, room_capacity, score)| SearchHit::Room {
+            room_number,
+            campus,
+            room_capacity,
+            score,
+        },
+    ).map_err(|e| format!("Failed to search rooms: {}", e))
+}
+
+fn search_exam_transactions(conn: &mut PooledConn, term: &str, limit: u32) -> Result<Vec<SearchHit>, String> {
+    // Exams are linked to students through their enrollment in the exam's class (or, for exams without
+    // a class, the subject), so a NIM finds every exam of that student. Archived periods are left out,
+    // as in the list commands.
+    let enrolled = r"EXISTS (SELECT 1 FROM enrollment
+        WHERE enrollment.nim = :term AND enrollment.subject_code = et.subject_code
+          AND (et.class_code IS NULL OR enrollment.class_code = et.class_code))";
+    conn.exec_map(
+        format!(
+            r"SELECT et.transaction_id, et.subject_code, subject.subject_name, et.room_number,
+                DATE_FORMAT(et.transaction_date, '%Y-%m-%d'), et.shift_id,
+                IF({enrolled}, :term, NULL) AS enrolled_nim,
+                GREATEST({}, {}, {}, {}, {}, IF({enrolled}, 90, 0)) AS score
+            FROM exam_transaction et
+            JOIN subject ON subject.subject_code = et.subject_code
+            WHERE NOT EXISTS (SELECT 1 FROM exam_period WHERE exam_period.period_id = et.period_id AND exam_period.archived = TRUE)
+            HAVING score > 0
+            ORDER BY score DESC, et.transaction_date DESC
+            LIMIT :limit",
+            match_score("et.transaction_id"),
+            match_score("et.subject_code"),
+            match_score("subject.subject_name"),
+            match_score("et.room_number"),
+            match_score("COALESCE(et.proctor, '')"),
+            enrolled = enrolled,
+        ),
+        search_params(term, limit),
+        |(transaction_id, subject_code, subject_name, room_number, transaction_date, shift_id, enrolled_nim, score)| SearchHit::ExamTransaction {
+            transaction_id,
+            subject_code,
+            subject_name,
+            room_number,
+            transaction_date,
+            shift_id,
+            enrolled_nim,
+            score,
+        },
+    ).map_err(|e| format!("Failed to search exam transactions: {}", e))
+}
+
+#[tauri::command]
+fn search(
+    query: String,
+    kinds: Option<Vec<String>>,
+    limit: Option<u32>,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<Vec<SearchHit>, String> {
+    require_role(&current_user, &[EXAM_COORDINATOR, SUBJECT_DEVELOPMENT])?;
+
+    let term = query.trim();
+    if term.chars().count() < 2 {
+        return Ok(Vec::new());
+    }
+
+    let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    let wants = |kind: &str| kinds.as_ref().map_or(true, |kinds| kinds.iter().any(|k| k == kind));
+
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+    let mut hits = Vec::new();
+
+    if wants("user") {
+        hits.extend(search_users(&mut conn, term, limit)?);
+    }
+    if wants("subject") {
+        hits.extend(search_subjects(&mut conn, term, limit)?);
+    }
+    if wants("room") {
+        hits.extend(search_rooms(&mut conn, term, limit)?);
+    }
+    if wants("exam_transaction") {
+        hits.extend(search_exam_transactions(&mut conn, term, limit)?);
+    }
+
+    hits.sort_by(|a, b| b.score().cmp(&a.score()));
+    hits.truncate(limit as usize);
+    Ok(hits)
+}
+
+fn current_user(state: &AppState) -> Result<User, String> {
+    let user_guard = state.user.lock().map_err(|e| format!("Failed to lock mutex: {}", e))?;
+    user_guard
+        .as_ref()
+        .map(|current_user| current_user.user.clone())
+        .ok_or_else(|| "Current user not authenticated".to_string())
+}
+
+#[tauri::command]
+fn get_my_exam_schedule(
+    period_id: Option<u32>,
+    mysql_pool: State<'_, Pool>,
+    current_user_state: State<'_, AppState>,
+) -> Result<StudentSchedule, String> {
+    // The NIM always comes from the session so a student can only ever read their own schedule.
+    let student = current_user(&current_user_state)?;
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    // Exams of a class only show up for students enrolled in that class. Once a student holds a seat
+    // in one room of a subject, the subject's other rooms are hidden. Makeup exams only show up for
+    // the students seated in them.
+    let rows: Vec<(StudentExam, bool)> = conn.exec_map(
+        r"SELECT DISTINCT et.transaction_id, et.subject_code, subject.subject_name,
+            DATE_FORMAT(et.transaction_date, '%Y-%m-%d') AS exam_date, TIME_FORMAT(shift.start_time, '%H:%i') AS exam_start,
+            TIME_FORMAT(shift.end_time, '%H:%i'), et.room_number, room.campus, seat.seat_number, et.status,
+            (et.transaction_date < CURDATE() OR (et.transaction_date = CURDATE() AND shift.end_time < CURTIME())) AS is_past
+        FROM enrollment
+        JOIN exam_transaction et ON et.subject_code = enrollment.subject_code
+        JOIN subject ON subject.subject_code = et.subject_code
+        JOIN shift ON shift.shift_id = et.shift_id
+        JOIN room ON room.room_number = et.room_number
+        LEFT JOIN seat_assignment seat ON seat.transaction_id = et.transaction_id AND seat.nim = enrollment.nim
+        WHERE enrollment.nim = :nim
+          AND (:period_id IS NULL OR et.period_id = :period_id)
+          AND (seat.nim IS NOT NULL OR (
+              et.makeup_of IS NULL
+              AND (et.class_code IS NULL OR enrollment.class_code = et.class_code)
+              AND NOT EXISTS (
+                  SELECT 1 FROM seat_assignment other_seat
+                  JOIN exam_transaction other_et ON other_et.transaction_id = other_seat.transaction_id
+                  WHERE other_seat.nim = :nim AND other_et.subject_code = et.subject_code
+              )
+          ))
+        ORDER BY exam_date, exam_start",
+        params! { "nim" => &student.nim, "period_id" => period_id },
+        |(transaction_id, subject_code, subject_name, transaction_date, start_time, end_time, room_number, campus, seat_number, status, is_past)| (
+            StudentExam {
+                transaction_id,
+                subject_code,
+                subject_name,
+                transaction_date,
+                start_time,
+                end_time,
+                room_number,
+                campus,
+                seat_number,
+                status,
+            },
+            is_past,
+        ),
+    ).map_err(|e| format!("Failed to fetch exam schedule: {}", e))?;
+
+    let (past, upcoming): (Vec<_>, Vec<_>) = rows.into_iter().partition(|(_, is_past)| *is_past);
+
+    Ok(StudentSchedule {
+        upcoming: upcoming.into_iter().map(|(exam, _)| exam).collect(),
+        past: past.into_iter().rev().map(|(exam, _)| exam).collect(),
+    })
+}
+
+// exam_transaction.proctor holds the assistant's bn_number; older rows may carry the initial instead.
+fn load_proctor_duties(
+    conn: &mut PooledConn,
+    proctor: &User,
+    date_from: Option<&str>,
+    date_to: Option<&str>,
+    exam_period_id: Option<u32>,
+    period: RosterPeriod,
+) -> Result<Vec<(ProctorDuty, String)>, String> {
+    let period_format = match period {
+        RosterPeriod::Week => "%x-W%v",
+        RosterPeriod::Month => "%Y-%m",
+    };
+
+    conn.exec_map(
+        r"SELECT et.transaction_id, et.subject_code, subject.subject_name,
+            DATE_FORMAT(et.transaction_date, '%Y-%m-%d'), TIME_FORMAT(shift.start_time, '%H:%i'),
+            TIME_FORMAT(shift.end_time, '%H:%i'), et.room_number, room.campus, et.status,
+            TIME_TO_SEC(TIMEDIFF(shift.end_time, shift.start_time)) / 3600.0,
+            DATE_FORMAT(et.transaction_date, :period_format)
+        FROM exam_transaction et
+        JOIN subject ON subject.subject_code = et.subject_code
+        JOIN shift ON shift.shift_id = et.shift_id
+        JOIN room ON room.room_number = et.room_number
+        WHERE (et.proctor = :bn_number OR et.proctor = :initial)
+          AND (:date_from IS NULL OR et.transaction_date >= :date_from)
+          AND (:date_to IS NULL OR et.transaction_date <= :date_to)
+          AND (:exam_period_id IS NULL OR et.period_id = :exam_period_id)
+        ORDER BY et.transaction_date, shift.start_time",
+        params! {
+            "bn_number" => proctor.bn_number.inner(),
+            // NULL for assistants without an initial, so legacy rows with an empty proctor never match.
+            "initial" => proctor.initial.as_deref(),
+            "date_from" => date_from,
+            "date_to" => date_to,
+            "exam_period_id" => exam_period_id,
+            "period_format" => period_format,
+        },
+        |(transaction_id, subject_code, subject_name, transaction_date, start_time, end_time, room_number, campus, status, hours, period_key)| (
+            ProctorDuty {
+                transaction_id,
+                subject_code,
+                subject_name,
+                transaction_date,
+                start_time,
+                end_time,
+                room_number,
+                campus,
+                status,
+                hours,
+            },
+            period_key,
+        ),
+    ).map_err(|e| format!("Failed to fetch duty roster: {}", e))
+}
+
+#[tauri::command]
+fn get_my_duty_roster(
+    date_from: Option<String>,
+    date_to: Option<String>,
+    exam_period_id: Option<u32>,
+    period: Option<RosterPeriod>,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<DutyRoster, String> {
+    let proctor = require_role(&current_user, &[ASSISTANT])?;
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    let rows = load_proctor_duties(
+        &mut conn,
+        &proctor,
+        date_from.as_deref(),
+        date_to.as_deref(),
+        exam_period_id,
+        period.unwrap_or_default(),
+    )?;
+
+    // Rows arrive in date order, so each period's duties are contiguous.
+    let mut workload: Vec<ProctorWorkload> = Vec::new();
+    for (duty, period_key) in &rows {
+        match workload.last_mut() {
+            Some(last) if last.period == *period_key => {
+                last.duties += 1;
+                last.hours += duty.hours;
+            }
+            _ => workload.push(ProctorWorkload {
+                period: period_key.clone(),
+                duties: 1,
+                hours: duty.hours,
+            }),
+        }
+    }
+
+    let total_hours = workload.iter().map(|period| period.hours).sum();
+
+    Ok(DutyRoster {
+        duties: rows.into_iter().map(|(duty, _)| duty).collect(),
+        workload,
+        total_hours,
+    })
+}
+
+const PEAK_DAY_COUNT: usize = 5;
+
+/// Booked versus available shifts and seats per room and campus between two dates (inclusive).
+/// A room/shift slot counts as available unless it falls on a closed day, a blackout for the room's
+/// campus or a maintenance window.
+#[tauri::command]
+fn get_room_utilization(
+    date_from: String,
+    date_to: String,
+    campus: Option<String>,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<UtilizationReport, String> {
+    require_role(&current_user, &[EXAM_COORDINATOR])?;
+    let parse = |value: &str| {
+        chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .map_err(|_| format!("Invalid date '{}', expected YYYY-MM-DD", value))
+    };
+    let (first_day, last_day) = (parse(&date_from)?, parse(&date_to)?);
+    if first_day > last_day {
+        return Err("date_from must not be after date_to".to_string());
+    }
+
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    let rooms: Vec<(String, String, i32, u32)> = conn.exec(
+        format!(
+            r"SELECT room.room_number, room.campus, room.room_capacity, {} FROM room
+            LEFT JOIN room_attribute ON room_attribute.room_number = room.room_number
+            WHERE :campus IS NULL OR room.campus = :campus
+            ORDER BY room.campus, room.room_number",
+            EFFECTIVE_CAPACITY_SQL
+        ),
+        params! { "campus" => &campus },
+    ).map_err(|e| format!("Failed to fetch rooms: {}", e))?;
+    let shifts: Vec<(String, String)> = conn.query(
+        "SELECT TIME_FORMAT(start_time, '%H:%i:%s'), TIME_FORMAT(end_time, '%H:%i:%s') FROM shift WHERE retired = FALSE",
+    ).map_err(|e| format!("Failed to fetch shifts: {}", e))?;
+    // Seats used are the allocated seats, or the class (or subject) enrollment before allocation.
+    let bookings: Vec<(String, String, String, String, bool, u32)> = conn.exec(
+        r"SELECT et.room_number, DATE_FORMAT(et.transaction_date, '%Y-%m-%d'),
+            TIME_FORMAT(shift.start_time, '%H:%i:%s'), TIME_FORMAT(shift.end_time, '%H:%i:%s'), shift.retired,
+            COALESCE(
+                NULLIF((SELECT COUNT(*) FROM seat_assignment seat WHERE seat.transaction_id = et.transaction_id), 0),
+                (SELECT COUNT(DISTINCT enrollment.nim) FROM enrollment
+                    WHERE enrollment.subject_code = et.subject_code
+                      AND (et.class_code IS NULL OR enrollment.class_code = et.class_code))
+            )
+        FROM exam_transaction et
+        JOIN shift ON shift.shift_id = et.shift_id
+        WHERE et.transaction_date BETWEEN :date_from AND :date_to",
+        params! { "date_from" => &date_from, "date_to" => &date_to },
+    ).map_err(|e| format!("Failed to fetch exam transactions: {}", e))?;
+    let blackouts = load_blackout_dates(&mut conn, Some(&date_from), Some(&date_to))?;
+    let unavailability = load_room_unavailability(&mut conn, None, Some(&date_from))?;
+
+    let mut days = Vec::new();
+    let mut day = first_day;
+    while day <= last_day {
+        days.push(day);
+        day = day.succ_opt().ok_or_else(|| "Date range is out of bounds".to_string())?;
+    }
+
+    let mut room_stats: BTreeMap<&str, Utilization> = BTreeMap::new();
+    let mut day_loads: BTreeMap<String, DayLoad> = days.iter()
+        .map(|day| {
+            let date = day.format("%Y-%m-%d").to_string();
+            (date.clone(), DayLoad { date, booked_shifts: 0, available_shifts: 0 })
+        })
+        .collect();
+
+    for (room_number, campus, _, _) in &rooms {
+        let stats = room_stats.entry(room_number.as_str()).or_default();
+        for day in &days {
+            if blackout_reason(*day, Some(campus.as_str()), &blackouts).is_some() {
+                continue;
+            }
+            let date = day.format("%Y-%m-%d").to_string();
+            let open_shifts = shifts.iter()
+                .filter(|(start_time, end_time)| {
+                    let start_at = format!("{} {}", date, start_time);
+                    let end_at = format!("{} {}", date, end_time);
+                    unavailability_overlapping(&unavailability, room_number, &start_at, &end_at).is_none()
+                })
+                .count() as u32;
+            stats.available_shifts += open_shifts;
+            if let Some(load) = day_loads.get_mut(&date) {
+                load.available_shifts += open_shifts;
+            }
+        }
+    }
+
+    // Only bookings in slots counted as available above, so shift utilization cannot exceed 1.
+    let room_details: HashMap<&str, (&str, u32)> = rooms.iter()
+        .map(|(room_number, campus, _, effective_capacity)| (room_number.as_str(), (campus.as_str(), *effective_capacity)))
+        .collect();
+    for (room_number, date, start_time, end_time, retired, seats_used) in &bookings {
+        let (Some(stats), Some((campus, effective_capacity))) = (room_stats.get_mut(room_number.as_str()), room_details.get(room_number.as_str())) else {
+            continue;
+        };
+        let Ok(day) = chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d") else {
+            continue;
+        };
+        let start_at = format!("{} {}", date, start_time);
+        let end_at = format!("{} {}", date, end_time);
+        if *retired
+            || blackout_reason(day, Some(*campus), &blackouts).is_some()
+            || unavailability_overlapping(&unavailability, room_number, &start_at, &end_at).is_some()
+        {
+            continue;
+        }
+        stats.booked_shifts += 1;
+        stats.seats_used += seats_used;
+        stats.seats_offered += effective_capacity;
+        if let Some(load) = day_loads.get_mut(date) {
+            load.booked_shifts += 1;
+        }
+    }
+
+    let mut total = Utilization::default();
+    let mut campus_stats: BTreeMap<String, CampusUtilization> = BTreeMap::new();
+    let mut idle_rooms = Vec::new();
+    let mut room_report = Vec::new();
+    for (room_number, campus, room_capacity, _) in &rooms {
+        let mut utilization = room_stats.remove(room_number.as_str()).unwrap_or_default();
+        utilization.finish();
+        if utilization.booked_shifts == 0 {
+            idle_rooms.push(room_number.clone());
+        }
+
+        total.add(&utilization);
+        let campus_entry = campus_stats.entry(campus.clone()).or_insert_with(|| CampusUtilization {
+            campus: campus.clone(),
+            rooms: 0,
+            utilization: Utilization::default(),
+        });
+        campus_entry.rooms += 1;
+        campus_entry.utilization.add(&utilization);
+
+        room_report.push(RoomUtilization {
+            room_number: room_number.clone(),
+            campus: campus.clone(),
+            room_capacity: *room_capacity,
+            utilization,
+        });
+    }
+    total.finish();
+
+    let mut peak_days: Vec<DayLoad> = day_loads.into_values().filter(|load| load.booked_shifts > 0).collect();
+    peak_days.sort_by(|a, b| b.booked_shifts.cmp(&a.booked_shifts).then_with(|| a.date.cmp(&b.date)));
+    peak_days.truncate(PEAK_DAY_COUNT);
+
+    Ok(UtilizationReport {
+        date_from,
+        date_to,
+        rooms: room_report,
+        campuses: campus_stats.into_values()
+            .map(|mut campus| {
+                campus.utilization.finish();
+                campus
+            })
+            .collect(),
+        total,
+        peak_days,
+        idle_rooms,
+    })
+}
+
+#[tauri::command]
+fn allocate_seats(
+    transaction_id: String,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<SeatAllocation, String> {
+    require_role(&current_user, &[EXAM_COORDINATOR])?;
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+    ensure_transaction_editable(&mut conn, &transaction_id)?;
+
+    let exam: Option<(String, u32, Option<String>, Option<String>, Option<String>)> = conn.exec_first(
+        format!(
+            r"SELECT et.subject_code, {}, et.makeup_of, et.class_code, et.status FROM exam_transaction et
+            JOIN room ON room.room_number = et.room_number
+            LEFT JOIN room_attribute ON room_attribute.room_number = room.room_number
+            WHERE et.transaction_id = :transaction_id",
+            EFFECTIVE_CAPACITY_SQL
+        ),
+        params! { "transaction_id" => &transaction_id },
+    ).map_err(|e| format!("Failed to execute query: {}", e))?;
+
+    let Some((subject_code, capacity, makeup_of, class_code, status)) = exam else {
+        return Err(format!("Exam transaction {} not found", transaction_id));
+    };
+    if is_cancelled(status.as_deref()) {
+        return Err(format!("Exam transaction {} is cancelled", transaction_id));
+    }
+
+    // A makeup exam only seats the students whose makeup request was scheduled into it. Otherwise,
+    // students already seated in another room of the same subject stay where they are.
+    let students: Vec<String> = if makeup_of.is_some() {
+        conn.exec(
+            "SELECT nim FROM makeup_request WHERE makeup_transaction_id = :transaction_id ORDER BY nim",
+            params! { "transaction_id" => &transaction_id },
+        )
+    } else {
+        conn.exec(
+            r"SELECT DISTINCT enrollment.nim FROM enrollment
+            WHERE enrollment.subject_code = :subject_code
+              AND (:class_code IS NULL OR enrollment.class_code = :class_code)
+              AND enrollment.nim NOT IN (
+                  SELECT seat.nim FROM seat_assignment seat
+                  JOIN exam_transaction et ON et.transaction_id = seat.transaction_id
+                  WHERE et.subject_code = :subject_code AND seat.transaction_id <> :transaction_id
+              )
+            ORDER BY enrollment.nim",
+            params! { "subject_code" => &subject_code, "class_code" => &class_code, "transaction_id" => &transaction_id },
+        )
+    }.map_err(|e| format!("Failed to fetch students: {}", e))?;
+
+    let split = students.len().min(capacity as usize);
+    let (seated, unseated) = students.split_at(split);
+
+    let mut transaction = conn.start_transaction(TxOpts::default())
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    transaction.exec_drop(
+        "DELETE FROM seat_assignment WHERE transaction_id = :transaction_id",
+        params! { "transaction_id" => &transaction_id },
+    ).map_err(|e| format!("Failed to clear seats: {}", e))?;
+
+    transaction.exec_batch(
+        "INSERT INTO seat_assignment (transaction_id, nim, seat_number) VALUES (:transaction_id, :nim, :seat_number)",
+        seated.iter().enumerate().map(|(index, nim)| params! {
+            "transaction_id" => &transaction_id,
+            "nim" => nim,
+            "seat_number" => index as u32 + 1,
+        }),
+    ).map_err(|e| format!("Failed to assign seats: {}", e))?;
+
+    transaction.commit()
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    Ok(SeatAllocation {
+        seated: seated.len() as u32,
+        unseated: unseated.to_vec(),
+    })
+}
+
+#[derive(Debug, Serialize)]
+pub struct RescheduleChange {
+    pub change_id: u32,
+    pub transaction_id: String,
+    pub subject_code: String,
+    pub old_date: String,
+    pub old_shift_id: String,
+    pub old_room_number: String,
+    pub new_date: String,
+    pub new_shift_id: String,
+    pub new_room_number: String,
+    pub reason: String,
+    pub changed_by: String,
+    pub changed_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AffectedStudent {
+    pub nim: String,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AffectedProctor {
+    pub bn_number: String,
+    pub initial: Option<String>,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RescheduleReport {
+    pub change: RescheduleChange,
+    pub students: Vec<AffectedStudent>,
+    pub proctor: Option<AffectedProctor>,
+    pub warnings: Vec<TravelWarning>,
+}
+
+fn load_reschedule_changes(conn: &mut PooledConn, condition: &str, params: mysql::Params) -> Result<Vec<RescheduleChange>, String> {
+    let rows: Vec<mysql::Row> = conn.exec(
+        format!(
+            r"SELECT change_id, exam_reschedule.transaction_id, et.subject_code,
+                DATE_FORMAT(old_date, '%Y-%m-%d') AS old_date, old_shift_id, old_room_number,
+                DATE_FORMAT(new_date, '%Y-%m-%d') AS new_date, new_shift_id, new_room_number,
+                reason, changed_by, DATE_FORMAT(changed_at, '%Y-%m-%d %H:%i:%s') AS changed_at
+            FROM exam_reschedule
+            JOIN exam_transaction et ON et.transaction_id = exam_reschedule.transaction_id
+            WHERE {}
+            ORDER BY changed_at DESC, change_id DESC",
+            condition
+        ),
+        params,
+    ).map_err(|e| format!("Failed to fetch reschedule history: {}", e))?;
+
+    rows.into_iter()
+        .map(|mut row| {
+            Ok(RescheduleChange {
+                change_id: take_column(&mut row, "change_id")?,
+                transaction_id: take_column(&mut row, "transaction_id")?,
+                subject_code: take_column(&mut row, "subject_code")?,
+                old_date: take_column(&mut row, "old_date")?,
+                old_shift_id: take_column(&mut row, "old_shift_id")?,
+                old_room_number: take_column(&mut row, "old_room_number")?,
+                new_date: take_column(&mut row, "new_date")?,
+                new_shift_id: take_column(&mut row, "new_shift_id")?,
+                new_room_number: take_column(&mut row, "new_room_number")?,
+                reason: take_column(&mut row, "reason")?,
+                changed_by: take_column(&mut row, "changed_by")?,
+                changed_at: take_column(&mut row, "changed_at")?,
+            })
+        })
+        .collect()
+}
+
+// Students who sit the exam: those seated in it, plus enrolled students of its class (or subject)
+// who hold no seat in another room of the subject. Mirrors what get_my_exam_schedule shows them.
+fn load_affected_students(conn: &mut PooledConn, transaction_id: &str) -> Result<Vec<AffectedStudent>, String> {
+    conn.exec_map(
+        r"SELECT DISTINCT users.nim, users.name FROM exam_transaction et
+        JOIN enrollment ON enrollment.subject_code = et.subject_code
+        JOIN users ON users.nim = enrollment.nim
+        LEFT JOIN seat_assignment seat ON seat.transaction_id = et.transaction_id AND seat.nim = enrollment.nim
+        WHERE et.transaction_id = :transaction_id
+          AND (seat.nim IS NOT NULL OR (
+              et.makeup_of IS NULL
+              AND (et.class_code IS NULL OR enrollment.class_code = et.class_code)
+              AND NOT EXISTS (
+                  SELECT 1 FROM seat_assignment other_seat
+                  JOIN exam_transaction other_et ON other_et.transaction_id = other_seat.transaction_id
+                  WHERE other_seat.nim = enrollment.nim AND other_et.subject_code = et.subject_code
+              )
+          ))
+        ORDER BY users.nim",
+        params! { "transaction_id" => transaction_id },
+        |(nim, name)| AffectedStudent { nim, name },
+    ).map_err(|e| format!("Failed to fetch affected students: {}", e))
+}
+
+fn room_effective_capacity(conn: &mut PooledConn, room_number: &str) -> Result<u32, String> {
+    let capacity: Option<u32> = conn.exec_first(
+        format!(
+            r"SELECT {} FROM room
+            LEFT JOIN room_attribute ON room_attribute.room_number = room.room_number
+            WHERE room.room_number = :room_number",
+            EFFECTIVE_CAPACITY_SQL
+        ),
+        params! { "room_number" => room_number },
+    ).map_err(|e| format!("Failed to execute query: {}", e))?;
+    capacity.ok_or_else(|| format!("Unknown room {}", room_number))
+}
+
+// `transaction_id` is the exam being moved, which may keep its own room; a new exam passes "".
+fn ensure_room_unbooked(
+    conn: &mut PooledConn,
+    room_number: &str,
+    transaction_date: &str,
+    shift_id: &str,
+    transaction_id: &str,
+) -> Result<(), String> {
+    let room_taken: Option<String> = conn.exec_first(
+        r"SELECT transaction_id FROM exam_transaction
+        WHERE room_number = :room_number AND transaction_date = :transaction_date AND shift_id = :shift_id
+          AND transaction_id <> :transaction_id
+        LIMIT 1",
+        params! {
+            "room_number" => room_number,
+            "transaction_date" => transaction_date,
+            "shift_id" => shift_id,
+            "transaction_id" => transaction_id,
+        },
+    ).map_err(|e| format!("Failed to execute query: {}", e))?;
+
+    match room_taken {
+        Some(other) => Err(format!("Room {} is already booked by {} in that slot", room_number, other)),
+        None => Ok(()),
+    }
+}
+
+// `proctor` may be a bn_number or an initial; exams are compared by the proctor's bn_number either way.
+fn ensure_proctor_unbooked(conn: &mut PooledConn, proctor: &str, transaction_id: &str) -> Result<(), String> {
+    let proctor_taken: Option<String> = conn.exec_first(
+        format!(
+            r"SELECT et.transaction_id FROM exam_transaction et
+            JOIN exam_transaction edited ON edited.transaction_id = :transaction_id
+            WHERE et.transaction_date = edited.transaction_date AND et.shift_id = edited.shift_id
+              AND et.transaction_id <> :transaction_id
+              AND {} = COALESCE(
+                  (SELECT users.bn_number FROM users WHERE users.bn_number = :proctor OR users.initial = :proctor LIMIT 1),
+                  :proctor
+              )
+            LIMIT 1",
+            PROCTOR_BN_SQL
+        ),
+        params! { "transaction_id" => transaction_id, "proctor" => proctor },
+    ).map_err(|e| format!("Failed to execute query: {}", e))?;
+
+    match proctor_taken {
+        Some(other) => Err(format!("Proctor {} is already assigned to {} in that slot", proctor, other)),
+        None => Ok(()),
+    }
+}
+
+// Fails when any of `nims` sits an exam of another subject in the slot, by the same rule as
+// load_affected_students: seated in it, or enrolled in its class (or subject) and seated nowhere else.
+fn ensure_students_free(
+    conn: &mut PooledConn,
+    nims: &HashSet<&str>,
+    subject_code: &str,
+    transaction_date: &str,
+    shift_id: &str,
+) -> Result<(), String> {
+    let clashes: Vec<(String, String)> = conn.exec(
+        r"SELECT DISTINCT enrollment.nim, other.subject_code FROM enrollment
+        JOIN exam_transaction other ON other.subject_code = enrollment.subject_code
+        LEFT JOIN seat_assignment seat ON seat.transaction_id = other.transaction_id AND seat.nim = enrollment.nim
+        WHERE other.transaction_date = :transaction_date AND other.shift_id = :shift_id
+          AND other.subject_code <> :subject_code
+          AND (seat.nim IS NOT NULL OR (
+              other.makeup_of IS NULL
+              AND (other.class_code IS NULL OR enrollment.class_code = other.class_code)
+              AND NOT EXISTS (
+                  SELECT 1 FROM seat_assignment other_seat
+                  JOIN exam_transaction seated_et ON seated_et.transaction_id = other_seat.transaction_id
+                  WHERE other_seat.nim = enrollment.nim AND seated_et.subject_code = other.subject_code
+              )
+          ))
+        ORDER BY enrollment.nim",
+        params! {
+            "transaction_date" => transaction_date,
+            "shift_id" => shift_id,
+            "subject_code" => subject_code,
+        },
+    ).map_err(|e| format!("Failed to check student clashes: {}", e))?;
+
+    let clashes: Vec<String> = clashes.into_iter()
+        .filter(|(nim, _)| nims.contains(nim.as_str()))
+        .map(|(nim, other_subject)| format!("{} ({})", nim, other_subject))
+        .collect();
+    if clashes.is_empty() {
+        return Ok(());
+    }
+    Err(format!(
+        "{} students already have another exam in that slot: {}",
+        clashes.len(),
+        clashes.iter().take(5).cloned().collect::<Vec<_>>().join(", ")
+    ))
+}
+
+#[tauri::command]
+fn get_reschedule_history(
+    transaction_id: Option<String>,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<Vec<RescheduleChange>, String> {
+    require_role(&current_user, &[EXAM_COORDINATOR, SUBJECT_DEVELOPMENT])?;
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    load_reschedule_changes(
+        &mut conn,
+        ":transaction_id IS NULL OR exam_reschedule.transaction_id = :transaction_id",
+        params! { "transaction_id" => transaction_id },
+    )
+}
+
+#[tauri::command]
+fn reschedule_exam_transaction(
+    transaction_id: String,
+    transaction_date: String,
+    shift_id: String,
+    room_number: String,
+    reason: String,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<RescheduleReport, String> {
+    let coordinator = require_role(&current_user, &[EXAM_COORDINATOR])?;
+    if reason.trim().is_empty() {
+        return Err("A reason is required to reschedule an exam".to_string());
+    }
+
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+    ensure_transaction_editable(&mut conn, &transaction_id)?;
+
+    let current: Option<(String, String, String, String, Option<u32>, Option<String>, Option<String>)> = conn.exec_first(
+        format!(
+            r"SELECT et.subject_code, DATE_FORMAT(et.transaction_date, '%Y-%m-%d'), et.shift_id, et.room_number, et.period_id, {}, et.status
+            FROM exam_transaction et WHERE et.transaction_id = :transaction_id",
+            PROCTOR_BN_SQL
+        ),
+        params! { "transaction_id" => &transaction_id },
+    ).map_err(|e| format!("Failed to execute query: {}", e))?;
+    let Some((subject_code, old_date, old_shift_id, old_room_number, period_id, proctor, status)) = current else {
+        return Err(format!("Exam transaction {} not found", transaction_id));
+    };
+    if is_cancelled(status.as_deref()) {
+        return Err(format!("Exam transaction {} is cancelled", transaction_id));
+    }
+
+    if (old_date.as_str(), old_shift_id.as_str(), old_room_number.as_str()) == (transaction_date.as_str(), shift_id.as_str(), room_number.as_str()) {
+        return Err(format!("Exam transaction {} is already scheduled in that slot", transaction_id));
+    }
+
+    // The exam stays in its own period; moving it into another one is a new exam, not a reschedule.
+    let periods = load_open_periods(&mut conn)?;
+    pick_exam_period(&periods, period_id, &transaction_date)?;
+    ensure_date_schedulable(&mut conn, &transaction_date, &room_number)?;
+    ensure_room_available(&mut conn, &room_number, &transaction_date, &shift_id)?;
+    ensure_room_unbooked(&mut conn, &room_number, &transaction_date, &shift_id, &transaction_id)?;
+
+    if let Some(proctor) = &proctor {
+        let proctor_taken: Option<String> = conn.exec_first(
+            format!(
+                r"SELECT et.transaction_id FROM exam_transaction et
+                WHERE et.transaction_date = :transaction_date AND et.shift_id = :shift_id
+                  AND et.transaction_id <> :transaction_id AND et.proctor IS NOT NULL AND {} = :proctor
+                LIMIT 1",
+                PROCTOR_BN_SQL
+            ),
+            params! {
+                "transaction_date" => &transaction_date,
+                "shift_id" => &shift_id,
+                "transaction_id" => &transaction_id,
+                "proctor" => proctor,
+            },
+        ).map_err(|e| format!("Failed to execute query: {}", e))?;
+        if let Some(other) = proctor_taken {
+            return Err(format!("Proctor {} already supervises {} in that slot", proctor, other));
+        }
+    }
+
+    let seated: Option<u32> = conn.exec_first(
+        "SELECT COUNT(*) FROM seat_assignment WHERE transaction_id = :transaction_id",
+        params! { "transaction_id" => &transaction_id },
+    ).map_err(|e| format!("Failed to execute query: {}", e))?;
+    let capacity = room_effective_capacity(&mut conn, &room_number)?;
+    let seated = seated.unwrap_or(0);
+    if seated > capacity {
+        return Err(format!("Room {} seats {} but {} students are already seated for this exam", room_number, capacity, seated));
+    }
+
+    let students = load_affected_students(&mut conn, &transaction_id)?;
+    let nims: HashSet<&str> = students.iter().map(|student| student.nim.as_str()).collect();
+    ensure_students_free(&mut conn, &nims, &subject_code, &transaction_date, &shift_id)?;
+
+    let mut transaction = conn.start_transaction(TxOpts::default())
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    transaction.exec_drop(
+        r"UPDATE exam_transaction
+        SET transaction_date = :transaction_date, shift_id = :shift_id, room_number = :room_number, revision = revision + 1
+        WHERE transaction_id = :transaction_id",
+        params! {
+            "transaction_date" => &transaction_date,
+            "shift_id" => &shift_id,
+            "room_number" => &room_number,
+            "transaction_id" => &transaction_id,
+        },
+    ).map_err(|e| format!("Failed to reschedule exam transaction: {}", e))?;
+
+    transaction.exec_drop(
+        r"INSERT INTO exam_reschedule (
+            transaction_id, old_date, old_shift_id, old_room_number,
+            new_date, new_shift_id, new_room_number, reason, changed_by
+        ) VALUES (
+            :transaction_id, :old_date, :old_shift_id, :old_room_number,
+            :new_date, :new_shift_id, :new_room_number, :reason, :changed_by
+        )",
+        params! {
+            "transaction_id" => &transaction_id,
+            "old_date" => &old_date,
+            "old_shift_id" => &old_shift_id,
+            "old_room_number" => &old_room_number,
+            "new_date" => &transaction_date,
+            "new_shift_id" => &shift_id,
+            "new_room_number" => &room_number,
+            "reason" => reason.trim(),
+            "changed_by" => coordinator.bn_number.inner(),
+        },
+    ).map_err(|e| format!("Failed to record reschedule: {}", e))?;
+    let change_id = transaction.last_insert_id().unwrap_or(0);
+
+    record_audit(
+        &mut transaction,
+        coordinator.bn_number.inner(),
+        "exam_rescheduled",
+        &transaction_id,
+        &format!(
+            "{} {} {} -> {} {} {}",
+            old_date, old_shift_id, old_room_number, transaction_date, shift_id, room_number
+        ),
+    )?;
+
+    transaction.commit()
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    let change = load_reschedule_changes(&mut conn, "exam_reschedule.change_id = :change_id", params! { "change_id" => change_id })?
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("Reschedule {} not found", change_id))?;
+
+    let (proctor, warnings) = match proctor {
+        Some(bn_number) => {
+            let details: Option<(Option<String>, String)> = conn.exec_first(
+                "SELECT initial, name FROM users WHERE bn_number = :bn_number",
+                params! { "bn_number" => &bn_number },
+            ).map_err(|e| format!("Failed to execute query: {}", e))?;
+            let slots = load_proctor_slots(
+                &mut conn,
+                &format!("{} = :proctor AND et.transaction_date = :transaction_date", PROCTOR_BN_SQL),
+                params! { "proctor" => &bn_number, "transaction_date" => &transaction_date },
+            )?;
+            let warnings = travel_warnings(slots, &load_travel_times(&mut conn)?)
+                .into_iter()
+                .filter(|warning| warning.from_exam == transaction_id || warning.to_exam == transaction_id)
+                .collect();
+            let (initial, name) = details.unwrap_or((None, bn_number.clone()));
+            (Some(AffectedProctor { bn_number, initial, name }), warnings)
+        }
+        None => (None, Vec::new()),
+    };
+
+    Ok(RescheduleReport {
+        change,
+        students,
+        proctor,
+        warnings,
+    })
+}
+
+// exam_transaction.status of an exam that will not take place; calendar exports mark it CANCELLED.
+const CANCELLED_STATUS: &str = "cancelled";
+
+fn is_cancelled(status: Option<&str>) -> bool {
+    status.is_some_and(|status| status.eq_ignore_ascii_case(CANCELLED_STATUS))
+}
+
+// A regular exam that can be made up: it is over or was cancelled, and its period is not archived.
+// Expects exam_transaction aliased as `et` and a :cancelled parameter bound to CANCELLED_STATUS.
+const MAKEUP_ELIGIBLE_SQL: &str = "(et.makeup_of IS NULL
+    AND (et.transaction_date < CURDATE() OR et.status = :cancelled)
+    AND NOT EXISTS (SELECT 1 FROM exam_period WHERE exam_period.period_id = et.period_id AND exam_period.archived = TRUE))";
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MakeupStatus {
+    Pending,
+    Approved,
+    Rejected,
+    Scheduled,
+}
+
+impl MakeupStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            MakeupStatus::Pending => "pending",
+            MakeupStatus::Approved => "approved",
+            MakeupStatus::Rejected => "rejected",
+            MakeupStatus::Scheduled => "scheduled",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct MakeupRequest {
+    pub request_id: u32,
+    pub nim: String,
+    pub student_name: String,
+    pub subject_code: String,
+    // The exam the student missed.
+    pub transaction_id: String,
+    pub reason: String,
+    pub status: String,
+    pub requested_by: String,
+    pub requested_at: String,
+    pub reviewed_by: Option<String>,
+    pub reviewed_at: Option<String>,
+    pub review_note: Option<String>,
+    pub makeup_transaction_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MakeupSchedule {
+    pub transaction_id: String,
+    pub period_id: u32,
+    pub students: Vec<String>,
+}
+
+fn load_makeup_requests(conn: &mut PooledConn, condition: &str, params: mysql::Params) -> Result<Vec<MakeupRequest>, String> {
+    let rows: Vec<mysql::Row> = conn.exec(
+        format!(
+            r"SELECT makeup_request.request_id, makeup_request.nim, users.name AS student_name, makeup_request.subject_code,
+                makeup_request.transaction_id, makeup_request.reason, makeup_request.status, makeup_request.requested_by,
+                DATE_FORMAT(makeup_request.requested_at, '%Y-%m-%d %H:%i:%s') AS requested_at, makeup_request.reviewed_by,
+                DATE_FORMAT(makeup_request.reviewed_at, '%Y-%m-%d %H:%i:%s') AS reviewed_at, makeup_request.review_note,
+                makeup_request.makeup_transaction_id
+            FROM makeup_request
+            JOIN users ON users.nim = makeup_request.nim
+            WHERE {}
+            ORDER BY makeup_request.requested_at, makeup_request.request_id",
+            condition
+        ),
+        params,
+    ).map_err(|e| format!("Failed to fetch makeup requests: {}", e))?;
+
+    rows.into_iter()
+        .map(|mut row| {
+            Ok(MakeupRequest {
+                request_id: take_column(&mut row, "request_id")?,
+                nim: take_column(&mut row, "nim")?,
+                student_name: take_column(&mut row, "student_name")?,
+                subject_code: take_column(&mut row, "subject_code")?,
+                transaction_id: take_column(&mut row, "transaction_id")?,
+                reason: take_column(&mut row, "reason")?,
+                status: take_column(&mut row, "status")?,
+                requested_by: take_column(&mut row, "requested_by")?,
+                requested_at: take_column(&mut row, "requested_at")?,
+                reviewed_by: take_column(&mut row, "reviewed_by")?,
+                reviewed_at: take_column(&mut row, "reviewed_at")?,
+                review_note: take_column(&mut row, "review_note")?,
+                makeup_transaction_id: take_column(&mut row, "makeup_transaction_id")?,
+            })
+        })
+        .collect()
+}
+
+/// Registers a makeup request for a missed exam. Students may only file for themselves; coordinators
+/// may file on a student's behalf. Without `transaction_id` the exam the student was seated in (or,
+/// failing that, the latest regular exam of the subject) is taken as the missed one.
+#[tauri::command]
+fn request_makeup_exam(
+    nim: String,
+    subject_code: String,
+    reason: String,
+    transaction_id: Option<String>,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<u32, String> {
+    let user = self::current_user(&current_user)?;
+    if user.nim != nim {
+        require_role(&current_user, &[EXAM_COORDINATOR])?;
+    }
+    if reason.trim().is_empty() {
+        return Err("A reason is required for a makeup request".to_string());
+    }
+
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    let enrolled: Option<u64> = conn.exec_first(
+        "SELECT COUNT(*) FROM enrollment WHERE nim = :nim AND subject_code = :subject_code",
+        params! { "nim" => &nim, "subject_code" => &subject_code },
+    ).map_err(|e| format!("Failed to execute query: {}", e))?;
+    if enrolled.unwrap_or(0) == 0 {
+        return Err(format!("{} is not enrolled in {}", nim, subject_code));
+    }
+
+    // Only an exam that is over (or was cancelled), outside an archived period, and that the student sat by
+    // the same rule as load_affected_students can be made up, also when the caller names the exam explicitly.
+    let missed: Option<String> = conn.exec_first(
+        format!(
+            r"SELECT et.transaction_id FROM exam_transaction et
+            LEFT JOIN seat_assignment seat ON seat.transaction_id = et.transaction_id AND seat.nim = :nim
+            WHERE et.subject_code = :subject_code AND {}
+              AND (:transaction_id IS NULL OR et.transaction_id = :transaction_id)
+              AND (seat.nim IS NOT NULL OR (
+                  EXISTS (
+                      SELECT 1 FROM enrollment
+                      WHERE enrollment.nim = :nim AND enrollment.subject_code = et.subject_code
+                        AND (et.class_code IS NULL OR enrollment.class_code = et.class_code)
+                  )
+                  AND NOT EXISTS (
+                      SELECT 1 FROM seat_assignment other_seat
+                      JOIN exam_transaction other_et ON other_et.transaction_id = other_seat.transaction_id
+                      WHERE other_seat.nim = :nim AND other_et.subject_code = et.subject_code
+                  )
+              ))
+            ORDER BY seat.nim IS NULL, et.transaction_date DESC
+            LIMIT 1",
+            MAKEUP_ELIGIBLE_SQL
+        ),
+        params! {
+            "nim" => &nim,
+            "subject_code" => &subject_code,
+            "transaction_id" => &transaction_id,
+            "cancelled" => CANCELLED_STATUS,
+        },
+    ).map_err(|e| format!("Failed to execute query: {}", e))?;
+    let Some(missed) = missed else {
+        return Err(match transaction_id {
+            Some(transaction_id) => format!(
+                "Exam transaction {} is not a past or cancelled regular exam of {} sat by {}",
+                transaction_id, subject_code, nim
+            ),
+            None => format!("{} has no past or cancelled exam of {} to make up", nim, subject_code),
+        });
+    };
+
+    let open_request: Option<u32> = conn.exec_first(
+        r"SELECT request_id FROM makeup_request
+        WHERE nim = :nim AND transaction_id = :transaction_id AND status <> :rejected",
+        params! { "nim" => &nim, "transaction_id" => &missed, "rejected" => MakeupStatus::Rejected.as_str() },
+    ).map_err(|e| format!("Failed to execute query: {}", e))?;
+    if let Some(request_id) = open_request {
+        return Err(format!("{} already has makeup request {} for exam {}", nim, request_id, missed));
+    }
+
+    conn.exec_drop(
+        r"INSERT INTO makeup_request (nim, subject_code, transaction_id, reason, status, requested_by)
+        VALUES (:nim, :subject_code, :transaction_id, :reason, :status, :requested_by)",
+        params! {
+            "nim" => &nim,
+            "subject_code" => &subject_code,
+            "transaction_id" => &missed,
+            "reason" => reason.trim(),
+            "status" => MakeupStatus::Pending.as_str(),
+            "requested_by" => user.bn_number.inner(),
+        },
+    ).map_err(|e| format!("Failed to create makeup request: {}", e))?;
+    let request_id = conn.last_insert_id() as u32;
+
+    record_audit(&mut conn, user.bn_number.inner(), "makeup_requested", &request_id.to_string(), &format!("{} {}", nim, missed))?;
+
+    Ok(request_id)
+}
+
+/// Cancels an exam that will not take place and returns the students who were due to sit it, so
+/// makeup requests can be filed for them. The exam stays on the calendar marked as cancelled.
+#[tauri::command]
+fn cancel_exam_transaction(
+    transaction_id: String,
+    reason: String,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<Vec<AffectedStudent>, String> {
+    let coordinator = require_role(&current_user, &[EXAM_COORDINATOR])?;
+    if reason.trim().is_empty() {
+        return Err("A reason is required to cancel an exam".to_string());
+    }
+
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+    ensure_transaction_editable(&mut conn, &transaction_id)?;
+
+    let status: Option<Option<String>> = conn.exec_first(
+        "SELECT status FROM exam_transaction WHERE transaction_id = :transaction_id",
+        params! { "transaction_id" => &transaction_id },
+    ).map_err(|e| format!("Failed to execute query: {}", e))?;
+    match status {
+        None => return Err(format!("Exam transaction {} not found", transaction_id)),
+        Some(status) if is_cancelled(status.as_deref()) => {
+            return Err(format!("Exam transaction {} is already cancelled", transaction_id));
+        }
+        Some(_) => {}
+    }
+
+    let students = load_affected_students(&mut conn, &transaction_id)?;
+
+    let mut transaction = conn.start_transaction(TxOpts::default())
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    transaction.exec_drop(
+        "UPDATE exam_transaction SET status = :status, revision = revision + 1 WHERE transaction_id = :transaction_id",
+        params! { "transaction_id" => &transaction_id, "status" => CANCELLED_STATUS },
+    ).map_err(|e| format!("Failed to cancel exam transaction: {}", e))?;
+
+    record_audit(&mut transaction, coordinator.bn_number.inner(), "exam_cancelled", &transaction_id, reason.trim())?;
+
+    transaction.commit()
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    Ok(students)
+}
+
+/// Coordinators see every request; anyone else only their own.
+#[tauri::command]
+fn get_makeup_requests(
+    status: Option<MakeupStatus>,
+    subject_code: Option<String>,
+    nim: Option<String>,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<Vec<MakeupRequest>, String> {
+    let nim = match require_role(&current_user, &[EXAM_COORDINATOR]) {
+        Ok(_) => nim,
+        Err(_) => Some(self::current_user(&current_user)?.nim),
+    };
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    load_makeup_requests(
+        &mut conn,
+        r"(:status IS NULL OR makeup_request.status = :status)
+          AND (:subject_code IS NULL OR makeup_request.subject_code = :subject_code)
+          AND (:nim IS NULL OR makeup_request.nim = :nim)",
+        params! {
+            "status" => status.map(|status| status.as_str()),
+            "subject_code" => subject_code,
+            "nim" => nim,
+        },
+    )
+}
+
+#[tauri::command]
+fn review_makeup_request(
+    request_id: u32,
+    approve: bool,
+    note: Option<String>,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<(), String> {
+    let coordinator = require_role(&current_user, &[EXAM_COORDINATOR])?;
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    let status: Option<String> = conn.exec_first(
+        "SELECT status FROM makeup_request WHERE request_id = :request_id",
+        params! { "request_id" => request_id },
+    ).map_err(|e| format!("Failed to execute query: {}", e))?;
+    match status {
+        None => return Err(format!("Makeup request {} not found", request_id)),
+        Some(status) if status != MakeupStatus::Pending.as_str() => {
+            return Err(format!("Makeup request {} is already {}", request_id, status));
+        }
+        Some(_) => {}
+    }
+
+    let decision = if approve { MakeupStatus::Approved } else { MakeupStatus::Rejected };
+    conn.exec_drop(
+        r"UPDATE makeup_request
+        SET status = :status, reviewed_by = :reviewed_by, reviewed_at = CURRENT_TIMESTAMP, review_note = :review_note
+        WHERE request_id = :request_id",
+        params! {
+            "status" => decision.as_str(),
+            "reviewed_by" => coordinator.bn_number.inner(),
+            "review_note" => note.as_deref().map(str::trim).filter(|note| !note.is_empty()),
+            "request_id" => request_id,
+        },
+    ).map_err(|e| format!("Failed to review makeup request: {}", e))?;
+
+    record_audit(
+        &mut conn,
+        coordinator.bn_number.inner(),
+        "makeup_reviewed",
+        &request_id.to_string(),
+        decision.as_str(),
+    )
+}
+
+/// Creates one makeup exam for every approved, not yet scheduled request of the missed exam
+/// `transaction_id`, seats those students in it and links it back to the original exam.
+#[tauri::command]
+fn schedule_makeup_exam(
+    transaction_id: String,
+    transaction_date: String,
+    shift_id: String,
+    room_number: String,
+    period_id: Option<u32>,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<MakeupSchedule, String> {
+    let coordinator = require_role(&current_user, &[EXAM_COORDINATOR])?;
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    // The same rule request_makeup_exam applied when the requests were filed.
+    let original: Option<(String, bool)> = conn.exec_first(
+        format!(
+            "SELECT et.subject_code, {} FROM exam_transaction et WHERE et.transaction_id = :transaction_id",
+            MAKEUP_ELIGIBLE_SQL
+        ),
+        params! { "transaction_id" => &transaction_id, "cancelled" => CANCELLED_STATUS },
+    ).map_err(|e| format!("Failed to execute query: {}", e))?;
+    let Some((subject_code, eligible)) = original else {
+        return Err(format!("Exam transaction {} not found", transaction_id));
+    };
+    if !eligible {
+        return Err(format!("Exam transaction {} is not a past or cancelled regular exam of an active period", transaction_id));
+    }
+
+    let students: Vec<(u32, String)> = conn.exec(
+        r"SELECT request_id, nim FROM makeup_request
+        WHERE transaction_id = :transaction_id AND status = :approved AND makeup_transaction_id IS NULL
+        ORDER BY nim",
+        params! { "transaction_id" => &transaction_id, "approved" => MakeupStatus::Approved.as_str() },
+    ).map_err(|e| format!("Failed to fetch makeup requests: {}", e))?;
+    if students.is_empty() {
+        return Err(format!("Exam transaction {} has no approved makeup requests to schedule", transaction_id));
+    }
+
+    // Makeups often run after the regular period, e.g. in a retake period, so the date picks the period.
+    let periods = load_open_periods(&mut conn)?;
+    let period_id = pick_exam_period(&periods, period_id, &transaction_date)?;
+    ensure_date_schedulable(&mut conn, &transaction_date, &room_number)?;
+    ensure_room_available(&mut conn, &room_number, &transaction_date, &shift_id)?;
+    ensure_room_unbooked(&mut conn, &room_number, &transaction_date, &shift_id, "")?;
+
+    let capacity = room_effective_capacity(&mut conn, &room_number)?;
+    if students.len() > capacity as usize {
+        return Err(format!("Room {} seats {} but {} students need a makeup", room_number, capacity, students.len()));
+    }
+
+    let nims: HashSet<&str> = students.iter().map(|(_, nim)| nim.as_str()).collect();
+    ensure_students_free(&mut conn, &nims, &subject_code, &transaction_date, &shift_id)?;
+
+    let mut taken: HashSet<String> = conn.query::<String, _>("SELECT transaction_id FROM exam_transaction")
+        .map_err(|e| format!("Failed to fetch exam transactions: {}", e))?
+        .into_iter()
+        .collect();
+    let makeup_id = next_transaction_id(&mut taken)?;
+
+    let mut transaction = conn.start_transaction(TxOpts::default())
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    transaction.exec_drop(
+        r"INSERT INTO exam_transaction (
+            transaction_id, subject_code, room_number, shift_id, transaction_date, period_id, makeup_of
+        ) VALUES (
+            :transaction_id, :subject_code, :room_number, :shift_id, :transaction_date, :period_id, :makeup_of
+        )",
+        params! {
+            "transaction_id" => &makeup_id,
+            "subject_code" => &subject_code,
+            "room_number" => &room_number,
+            "shift_id" => &shift_id,
+            "transaction_date" => &transaction_date,
+            "period_id" => period_id,
+            "makeup_of" => &transaction_id,
+        },
+    ).map_err(|e| format!("Failed to create makeup exam: {}", e))?;
+
+    transaction.exec_batch(
+        "INSERT INTO seat_assignment (transaction_id, nim, seat_number) VALUES (:transaction_id, :nim, :seat_number)",
+        students.iter().enumerate().map(|(index, (_, nim))| params! {
+            "transaction_id" => &makeup_id,
+            "nim" => nim,
+            "seat_number" => index as u32 + 1,
+        }),
+    ).map_err(|e| format!("Failed to assign seats: {}", e))?;
+
+    transaction.exec_batch(
+        "UPDATE makeup_request SET status = :status, makeup_transaction_id = :makeup_transaction_id WHERE request_id = :request_id",
+        students.iter().map(|(request_id, _)| params! {
+            "status" => MakeupStatus::Scheduled.as_str(),
+            "makeup_transaction_id" => &makeup_id,
+            "request_id" => request_id,
+        }),
+    ).map_err(|e| format!("Failed to update makeup requests: {}", e))?;
+
+    transaction.commit()
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    record_audit(
+        &mut conn,
+        coordinator.bn_number.inner(),
+        "makeup_scheduled",
+        &makeup_id,
+        &format!("makeup of {} for {} students", transaction_id, students.len()),
+    )?;
+
+    Ok(MakeupSchedule {
+        transaction_id: makeup_id,
+        period_id,
+        students: students.into_iter().map(|(_, nim)| nim).collect(),
+    })
+}
+
+struct CalendarEvent {
+    transaction_id: String,
+    subject_code: String,
+    subject_name: String,
+    date: String,
+    start_time: String,
+    end_time: String,
+    room_number: String,
+    campus: String,
+    proctor_name: Option<String>,
+    status: Option<String>,
+    revision: u32,
+}
+
+fn escape_ics_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+// RFC 5545 content lines are folded at 75 octets, continuing with a single leading space.
+fn fold_ics_line(line: &str, output: &mut String) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            output.push_str("\r\n ");
+            width = 1;
+        }
+        output.push(c);
+        width += c.len_utf8();
+    }
+    output.push_str("\r\n");
+}
+
+fn load_calendar_events(conn: &mut PooledConn, condition: &str, params: mysql::Params) -> Result<Vec<CalendarEvent>, String> {
+    conn.exec_map(
+        format!(
+            r"SELECT et.transaction_id, et.subject_code, subject.subject_name,
+                DATE_FORMAT(et.transaction_date, '%Y%m%d'), TIME_FORMAT(shift.start_time, '%H%i%s'),
+                TIME_FORMAT(shift.end_time, '%H%i%s'), et.room_number, room.campus,
+                (SELECT users.name FROM users WHERE users.bn_number = et.proctor OR users.initial = et.proctor LIMIT 1),
+                et.status, et.revision
+            FROM exam_transaction et
+            JOIN subject ON subject.subject_code = et.subject_code
+            JOIN shift ON shift.shift_id = et.shift_id
+            JOIN room ON room.room_number = et.room_number
+            WHERE {}
+            ORDER BY et.transaction_date, shift.start_time",
+            condition
+        ),
+        params,
+        |(transaction_id, subject_code, subject_name, date, start_time, end_time, room_number, campus, proctor_name, status, revision)| CalendarEvent {
+            transaction_id,
+            subject_code,
+            subject_name,
+            date,
+            start_time,
+            end_time,
+            room_number,
+            campus,
+            proctor_name,
+            status,
+            revision,
+        },
+    ).map_err(|e| format!("Failed to fetch calendar events: {}", e))
+}
+
+fn render_ics(conn: &mut PooledConn, calendar_name: &str, events: &[CalendarEvent]) -> Result<String, String> {
+    let dtstamp: Option<String> = conn.query_first("SELECT DATE_FORMAT(UTC_TIMESTAMP(), '%Y%m%dT%H%i%sZ')")
+        .map_err(|e| format!("Failed to read server time: {}", e))?;
+    let dtstamp = dtstamp.unwrap_or_default();
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//SLC//SR Exam//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        format!("X-WR-CALNAME:{}", escape_ics_text(calendar_name)),
+        format!("X-WR-TIMEZONE:{}", CALENDAR_TIMEZONE),
+        "BEGIN:VTIMEZONE".to_string(),
+        format!("TZID:{}", CALENDAR_TIMEZONE),
+        "BEGIN:STANDARD".to_string(),
+        "DTSTART:19700101T000000".to_string(),
+        "TZOFFSETFROM:+0700".to_string(),
+        "TZOFFSETTO:+0700".to_string(),
+        "TZNAME:WIB".to_string(),
+        "END:STANDARD".to_string(),
+        "END:VTIMEZONE".to_string(),
+    ];
+
+    for event in events {
+        let cancelled = is_cancelled(event.status.as_deref());
+        let mut description = format!("Subject: {} - {}\nRoom: {} ({})", event.subject_code, event.subject_name, event.room_number, event.campus);
+        if let Some(proctor_name) = &event.proctor_name {
+            description.push_str(&format!("\nProctor: {}", proctor_name));
+        }
+
+        lines.extend([
+            "BEGIN:VEVENT".to_string(),
+            // The UID never changes for a transaction, so re-imports update the existing event.
+            format!("UID:{}@sr-exam", event.transaction_id),
+            format!("SEQUENCE:{}", event.revision),
+            format!("DTSTAMP:{}", dtstamp),
+            format!("DTSTART;TZID={}:{}T{}", CALENDAR_TIMEZONE, event.date, event.start_time),
+            format!("DTEND;TZID={}:{}T{}", CALENDAR_TIMEZONE, event.date, event.end_time),
+            format!("SUMMARY:{}", escape_ics_text(&format!("{} Exam - {}", event.subject_code, event.subject_name))),
+            format!("LOCATION:{}", escape_ics_text(&format!("{}, {}", event.room_number, event.campus))),
+            format!("DESCRIPTION:{}", escape_ics_text(&description)),
+            format!("STATUS:{}", if cancelled { "CANCELLED" } else { "CONFIRMED" }),
+            "END:VEVENT".to_string(),
+        ]);
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut output = String::new();
+    for line in &lines {
+        fold_ics_line(line, &mut output);
+    }
+    Ok(output)
+}
+
+#[tauri::command]
+fn export_timetable_ics(mysql_pool: State<'_, Pool>, current_user: State<'_, AppState>) -> Result<String, String> {
+    require_role(&current_user, &[EXAM_COORDINATOR])?;
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    let events = load_calendar_events(&mut conn, "1 = 1", mysql::Params::Empty)?;
+    render_ics(&mut conn, "Exam Timetable", &events)
+}
+
+#[tauri::command]
+fn export_student_ics(nim: String, mysql_pool: State<'_, Pool>, current_user: State<'_, AppState>) -> Result<String, String> {
+    let user = self::current_user(&current_user)?;
+    if user.nim != nim {
+        require_role(&current_user, &[EXAM_COORDINATOR])?;
+    }
+
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    // Same visibility rule as get_my_exam_schedule: a seat in one room hides the subject's other rooms.
+    let events = load_calendar_events(
+        &mut conn,
+        r"et.subject_code IN (SELECT subject_code FROM enrollment WHERE nim = :nim)
+          AND (EXISTS (SELECT 1 FROM seat_assignment seat WHERE seat.transaction_id = et.transaction_id AND seat.nim = :nim)
+               OR et.makeup_of IS NULL
+                  AND (et.class_code IS NULL
+                       OR EXISTS (SELECT 1 FROM enrollment WHERE enrollment.nim = :nim AND enrollment.class_code = et.class_code))
+                  AND NOT EXISTS (
+                      SELECT 1 FROM seat_assignment other_seat
+                      JOIN exam_transaction other_et ON other_et.transaction_id = other_seat.transaction_id
+                      WHERE other_seat.nim = :nim AND other_et.subject_code = et.subject_code
+                  ))",
+        params! { "nim" => &nim },
+    )?;
+    render_ics(&mut conn, &format!("Exam Schedule {}", nim), &events)
+}
+
+#[tauri::command]
+fn export_proctor_ics(initial: String, mysql_pool: State<'_, Pool>, current_user: State<'_, AppState>) -> Result<String, String> {
+    let user = self::current_user(&current_user)?;
+    if user.initial.as_deref() != Some(initial.as_str()) {
+        require_role(&current_user, &[EXAM_COORDINATOR])?;
+    }
+
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    let events = load_calendar_events(
+        &mut conn,
+        "(et.proctor = :initial OR et.proctor IN (SELECT bn_number FROM users WHERE initial = :initial))",
+        params! { "initial" => &initial },
+    )?;
+    render_ics(&mut conn, &format!("Proctor Duties {}", initial), &events)
+}
+
+fn load_exam_report_rows(conn: &mut PooledConn, filter: ExamTransactionFilter) -> Result<Vec<ExamReportRow>, String> {
+    let filters = exam_transaction_filters(filter);
+
+    // enrolled_count is the students expected in the room: its seats once allocated, otherwise the
+    // enrollment of the exam's class (or subject).
+    let rows: Vec<mysql::Row> = conn.exec(
+        format!(
+            r"SELECT exam_transaction.transaction_id, exam_transaction.subject_code, subject.subject_name,
+                DATE_FORMAT(exam_transaction.transaction_date, '%Y-%m-%d') AS transaction_date, exam_transaction.shift_id,
+                TIME_FORMAT(shift.start_time, '%H:%i') AS start_time, TIME_FORMAT(shift.end_time, '%H:%i') AS end_time,
+                exam_transaction.room_number, room.campus, exam_transaction.proctor,
+                (SELECT users.name FROM users
+                    WHERE users.bn_number = exam_transaction.proctor OR users.initial = exam_transaction.proctor
+                    LIMIT 1) AS proctor_name,
+                exam_transaction.status,
+                COALESCE(
+                    NULLIF((SELECT COUNT(*) FROM seat_assignment
+                        WHERE seat_assignment.transaction_id = exam_transaction.transaction_id), 0),
+                    (SELECT COUNT(DISTINCT enrollment.nim) FROM enrollment
+                        WHERE enrollment.subject_code = exam_transaction.subject_code
+                          AND (exam_transaction.class_code IS NULL OR enrollment.class_code = exam_transaction.class_code))
+                ) AS enrolled_count,
+                (SELECT COUNT(*) FROM seat_assignment
+                    WHERE seat_assignment.transaction_id = exam_transaction.transaction_id) AS seated_count
+            FROM exam_transaction
+            JOIN room ON room.room_number = exam_transaction.room_number
+            JOIN subject ON subject.subject_code = exam_transaction.subject_code
+            JOIN shift ON shift.shift_id = exam_transaction.shift_id{}
+            ORDER BY exam_transaction.transaction_date, shift.start_time, exam_transaction.room_number",
+            filters.where_clause()
+        ),
+        filters.params(),
+    ).map_err(|e| format!("Failed to fetch report rows: {}", e))?;
+
+    rows.into_iter()
+        .map(|mut row| {
+            Ok(ExamReportRow {
+                transaction_id: take_column(&mut row, "transaction_id")?,
+                subject_code: take_column(&mut row, "subject_code")?,
+                subject_name: take_column(&mut row, "subject_name")?,
+                transaction_date: take_column(&mut row, "transaction_date")?,
+                shift_id: take_column(&mut row, "shift_id")?,
+                start_time: take_column(&mut row, "start_time")?,
+                end_time: take_column(&mut row, "end_time")?,
+                room_number: take_column(&mut row, "room_number")?,
+                campus: take_column(&mut row, "campus")?,
+                proctor: take_column(&mut row, "proctor")?,
+                proctor_name: take_column(&mut row, "proctor_name")?,
+                status: take_column(&mut row, "status")?,
+                enrolled_count: take_column(&mut row, "enrolled_count")?,
+                seated_count: take_column(&mut row, "seated_count")?,
+            })
+        })
+        .collect()
+}
+
+fn write_csv_report(path: &std::path::Path, columns: &[ReportColumn], rows: &[ExamReportRow]) -> Result<(), String> {
+    let mut writer = csv::Writer::from_path(path).map_err(|e| format!("Failed to create CSV file: {}", e))?;
+
+    writer.write_record(columns.iter().map(|column| column.header()))
+        .map_err(|e| format!("Failed to write CSV: {}", e))?;
+
+    for row in rows {
+        writer.write_record(columns.iter().map(|column| match row.cell(*column) {
+            ReportCell::Text(value) => value,
+            ReportCell::Number(value) => value.to_string(),
+        })).map_err(|e| format!("Failed to write CSV: {}", e))?;
+    }
+
+    writer.flush().map_err(|e| format!("Failed to write CSV: {}", e))
+}
+
+fn write_xlsx_report(path: &std::path::Path, columns: &[ReportColumn], rows: &[ExamReportRow]) -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let header_format = Format::new().set_bold();
+    let worksheet = workbook.add_worksheet();
+    worksheet.set_name("Exam Transactions")?;
+
+    for (col, column) in columns.iter().enumerate() {
+        worksheet.write_string_with_format(0, col as u16, column.header(), &header_format)?;
+    }
+
+    for (index, row) in rows.iter().enumerate() {
+        let excel_row = index as u32 + 1;
+        for (col, column) in columns.iter().enumerate() {
+            match row.cell(*column) {
+                ReportCell::Text(value) => worksheet.write_string(excel_row, col as u16, value)?,
+                ReportCell::Number(value) => worksheet.write_number(excel_row, col as u16, value)?,
+            };
+        }
+    }
+
+    worksheet.set_freeze_panes(1, 0)?;
+    worksheet.autofit();
+    workbook.save(path)
 }
 
 #[tauri::command]
-async fn get_password_by_nim(_nim: String) -> Result<String, ()> {
-    let operation = PasswordbyNIM::build({});
-    let response = surf::post("https://academic-slc.apps.binus.ac.id/tpa-241/query")
-        .run_graphql(operation)
-        .await;
+async fn export_exam_report(
+    format: ReportFormat,
+    columns: Option<Vec<ReportColumn>>,
+    filter: ExamTransactionFilter,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<Option<String>, String> {
+    require_role(&current_user, &[EXAM_COORDINATOR, SUBJECT_DEVELOPMENT])?;
+
+    let columns = columns.filter(|columns| !columns.is_empty()).unwrap_or_else(|| ReportColumn::ALL.to_vec());
+    let rows = {
+        let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+        load_exam_report_rows(&mut conn, filter)?
+    };
+
+    let (filter_name, extension) = match format {
+        ReportFormat::Csv => ("CSV", "csv"),
+        ReportFormat::Xlsx => ("Excel Workbook", "xlsx"),
+    };
+
+    // Blocking dialog is fine here: async commands never run on the main thread.
+    let Some(path) = FileDialogBuilder::new()
+        .add_filter(filter_name, &[extension])
+        .set_file_name(&format!("exam-report.{}", extension))
+        .save_file()
+    else {
+        return Ok(None);
+    };
+
+    match format {
+        ReportFormat::Csv => write_csv_report(&path, &columns, &rows)?,
+        ReportFormat::Xlsx => write_xlsx_report(&path, &columns, &rows).map_err(|e| format!("Failed to write workbook: {}", e))?,
+    }
+
+    Ok(Some(path.to_string_lossy().into_owned()))
+}
+
+const SHEET_PAGE_WIDTH: f32 = 210.0;
+const SHEET_PAGE_HEIGHT: f32 = 297.0;
+const SHEET_MARGIN: f32 = 20.0;
+const SHEET_TABLE_TOP: f32 = 232.0;
+
+struct SheetSeat {
+    seat_number: u32,
+    nim: String,
+    name: String,
+}
+
+struct ExamSheet {
+    transaction_id: String,
+    subject_code: String,
+    subject_name: String,
+    date: String,
+    shift_id: String,
+    start_time: String,
+    end_time: String,
+    room_number: String,
+    campus: String,
+    proctor_name: Option<String>,
+    seats: Vec<SheetSeat>,
+}
+
+#[derive(Clone, Copy)]
+enum SheetKind {
+    DoorList,
+    Attendance,
+}
+
+impl SheetKind {
+    fn title(&self) -> &'static str {
+        match self {
+            SheetKind::DoorList => "DOOR LIST",
+            SheetKind::Attendance => "ATTENDANCE SHEET",
+        }
+    }
+
+    // Attendance rows are taller to leave room for a signature.
+    fn row_height(&self) -> f32 {
+        match self {
+            SheetKind::DoorList => 7.0,
+            SheetKind::Attendance => 10.0,
+        }
+    }
+}
+
+struct SheetFonts {
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+}
+
+fn load_exam_sheets(conn: &mut PooledConn, condition: &str, params: mysql::Params) -> Result<Vec<ExamSheet>, String> {
+    let mut sheets = conn.exec_map(
+        format!(
+            r"SELECT et.transaction_id, et.subject_code, subject.subject_name,
+                DATE_FORMAT(et.transaction_date, '%d %M %Y'), et.shift_id,
+                TIME_FORMAT(shift.start_time, '%H:%i'), TIME_FORMAT(shift.end_time, '%H:%i'),
+                et.room_number, room.campus,
+                (SELECT users.name FROM users WHERE users.bn_number = et.proctor OR users.initial = et.proctor LIMIT 1)
+            FROM exam_transaction et
+            JOIN subject ON subject.subject_code = et.subject_code
+            JOIN shift ON shift.shift_id = et.shift_id
+            JOIN room ON room.room_number = et.room_number
+            WHERE {}
+            ORDER BY et.room_number, shift.start_time",
+            condition
+        ),
+        params,
+        |(transaction_id, subject_code, subject_name, date, shift_id, start_time, end_time, room_number, campus, proctor_name)| ExamSheet {
+            transaction_id,
+            subject_code,
+            subject_name,
+            date,
+            shift_id,
+            start_time,
+            end_time,
+            room_number,
+            campus,
+            proctor_name,
+            seats: Vec::new(),
+        },
+    ).map_err(|e| format!("Failed to fetch exam transactions: {}", e))?;
+
+    for sheet in &mut sheets {
+        sheet.seats = conn.exec_map(
+            r"SELECT seat.seat_number, seat.nim, users.name FROM seat_assignment seat
+            JOIN users ON users.nim = seat.nim
+            WHERE seat.transaction_id = :transaction_id
+            ORDER BY seat.seat_number",
+            params! { "transaction_id" => &sheet.transaction_id },
+            |(seat_number, nim, name)| SheetSeat { seat_number, nim, name },
+        ).map_err(|e| format!("Failed to fetch seats: {}", e))?;
+    }
 
-    match response {
-        Ok(response) => {
-            let data = response.data.unwrap();
-            Ok(data.get_password_by_nim)
+    Ok(sheets)
+}
+
+// Splits off the exams that have no seats allocated yet, returning their transaction ids.
+fn take_unallocated_sheets(sheets: &mut Vec<ExamSheet>) -> Vec<String> {
+    let (allocated, unallocated): (Vec<_>, Vec<_>) = std::mem::take(sheets).into_iter().partition(|sheet| !sheet.seats.is_empty());
+    *sheets = allocated;
+    unallocated.into_iter().map(|sheet| sheet.transaction_id).collect()
+}
+
+fn draw_rule(layer: &PdfLayerReference, y: f32, from_x: f32, to_x: f32) {
+    layer.add_line(Line {
+        points: vec![(Point::new(Mm(from_x), Mm(y)), false), (Point::new(Mm(to_x), Mm(y)), false)],
+        is_closed: false,
+    });
+}
+
+fn truncate_text(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        text.to_string()
+    } else {
+        format!("{}...", text.chars().take(max_chars - 3).collect::<String>())
+    }
+}
+
+fn render_sheet(
+    sheet: &ExamSheet,
+    kind: SheetKind,
+    fonts: &SheetFonts,
+    next_layer: &mut dyn FnMut() -> PdfLayerReference,
+) {
+    let right_edge = SHEET_PAGE_WIDTH - SHEET_MARGIN;
+    // Attendance pages keep space at the bottom for the proctor's signature.
+    let bottom = match kind {
+        SheetKind::DoorList => SHEET_MARGIN,
+        SheetKind::Attendance => SHEET_MARGIN + 20.0,
+    };
+    let rows_per_page = ((SHEET_TABLE_TOP - bottom) / kind.row_height()) as usize - 1;
+    let page_count = sheet.seats.len().div_ceil(rows_per_page);
+
+    for (page_index, seats) in sheet.seats.chunks(rows_per_page).enumerate() {
+        let layer = next_layer();
+        let top = SHEET_PAGE_HEIGHT - SHEET_MARGIN;
+
+        layer.use_text(kind.title(), 16.0, Mm(SHEET_MARGIN), Mm(top), &fonts.bold);
+        layer.use_text(format!("Page {} of {}", page_index + 1, page_count), 9.0, Mm(right_edge - 22.0), Mm(top), &fonts.regular);
+        layer.use_text(
+            truncate_text(&format!("{} - {}", sheet.subject_code, sheet.subject_name), 70),
+            12.0, Mm(SHEET_MARGIN), Mm(top - 10.0), &fonts.bold,
+        );
+        layer.use_text(
+            format!("{}    Shift {} ({} - {})", sheet.date, sheet.shift_id, sheet.start_time, sheet.end_time),
+            11.0, Mm(SHEET_MARGIN), Mm(top - 17.0), &fonts.regular,
+        );
+        layer.use_text(format!("Room {} ({})", sheet.room_number, sheet.campus), 11.0, Mm(SHEET_MARGIN), Mm(top - 23.0), &fonts.regular);
+        layer.use_text(
+            format!("Proctor: {}", sheet.proctor_name.as_deref().unwrap_or("-")),
+            11.0, Mm(SHEET_MARGIN), Mm(top - 29.0), &fonts.regular,
+        );
+        layer.use_text(format!("Transaction {}", sheet.transaction_id), 9.0, Mm(SHEET_MARGIN), Mm(top - 35.0), &fonts.regular);
+
+        layer.use_text("Seat", 10.0, Mm(SHEET_MARGIN), Mm(SHEET_TABLE_TOP), &fonts.bold);
+        layer.use_text("NIM", 10.0, Mm(SHEET_MARGIN + 15.0), Mm(SHEET_TABLE_TOP), &fonts.bold);
+        layer.use_text("Name", 10.0, Mm(SHEET_MARGIN + 45.0), Mm(SHEET_TABLE_TOP), &fonts.bold);
+        if let SheetKind::Attendance = kind {
+            layer.use_text("Signature", 10.0, Mm(right_edge - 40.0), Mm(SHEET_TABLE_TOP), &fonts.bold);
+        }
+        draw_rule(&layer, SHEET_TABLE_TOP - 2.0, SHEET_MARGIN, right_edge);
+
+        let name_width = match kind {
+            SheetKind::DoorList => 60,
+            SheetKind::Attendance => 38,
+        };
+        for (row, seat) in seats.iter().enumerate() {
+            let y = SHEET_TABLE_TOP - kind.row_height() * (row as f32 + 1.0);
+            layer.use_text(seat.seat_number.to_string(), 10.0, Mm(SHEET_MARGIN), Mm(y), &fonts.regular);
+            layer.use_text(seat.nim.as_str(), 10.0, Mm(SHEET_MARGIN + 15.0), Mm(y), &fonts.regular);
+            layer.use_text(truncate_text(&seat.name, name_width), 10.0, Mm(SHEET_MARGIN + 45.0), Mm(y), &fonts.regular);
+            if let SheetKind::Attendance = kind {
+                draw_rule(&layer, y - 3.0, SHEET_MARGIN, right_edge);
+            }
+        }
+
+        if let SheetKind::Attendance = kind {
+            layer.use_text("Present: ______    Absent: ______", 10.0, Mm(SHEET_MARGIN), Mm(SHEET_MARGIN + 8.0), &fonts.regular);
+            layer.use_text("Proctor signature: ____________________", 10.0, Mm(right_edge - 75.0), Mm(SHEET_MARGIN + 8.0), &fonts.regular);
         }
-        Err(_err) => Err(()),
     }
 }
 
+// Each exam gets its door list followed by its attendance sheet.
+fn render_exam_sheets_pdf(title: &str, sheets: &[ExamSheet]) -> Result<Vec<u8>, String> {
+    let (doc, first_page, first_layer) = PdfDocument::new(title, Mm(SHEET_PAGE_WIDTH), Mm(SHEET_PAGE_HEIGHT), "Layer 1");
+    let fonts = SheetFonts {
+        regular: doc.add_builtin_font(BuiltinFont::Helvetica).map_err(|e| format!("Failed to load font: {}", e))?,
+        bold: doc.add_builtin_font(BuiltinFont::HelveticaBold).map_err(|e| format!("Failed to load font: {}", e))?,
+    };
+
+    let mut unused_first_page = Some((first_page, first_layer));
+    let mut next_layer = || {
+        let (page, layer) = unused_first_page.take()
+            .unwrap_or_else(|| doc.add_page(Mm(SHEET_PAGE_WIDTH), Mm(SHEET_PAGE_HEIGHT), "Layer 1"));
+        doc.get_page(page).get_layer(layer)
+    };
+
+    for sheet in sheets {
+        render_sheet(sheet, SheetKind::DoorList, &fonts, &mut next_layer);
+        render_sheet(sheet, SheetKind::Attendance, &fonts, &mut next_layer);
+    }
+
+    doc.save_to_bytes().map_err(|e| format!("Failed to render PDF: {}", e))
+}
+
+fn safe_file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect()
+}
+
 #[tauri::command]
-async fn get_room_transaction(mysql_pool: State<'_, Pool>, selected_date: String, room_number: Option<String>) -> Result<Vec<RoomTransaction>, String> {
-    let mut conn: PooledConn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
-    
-    let query = match room_number {
-        Some(ref room) => "SELECT room_number, shift_id FROM exam_transaction WHERE transactionDate = :selected_date AND room_number = :room_number",
-        None => "SELECT room_number, shift_id FROM exam_transaction WHERE transactionDate = :selected_date",
+async fn export_exam_sheets(
+    transaction_id: String,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<Option<String>, String> {
+    require_role(&current_user, &[EXAM_COORDINATOR, ASSISTANT])?;
+
+    let mut sheets = {
+        let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+        load_exam_sheets(&mut conn, "et.transaction_id = :transaction_id", params! { "transaction_id" => &transaction_id })?
     };
+    if sheets.is_empty() {
+        return Err(format!("Exam transaction {} not found", transaction_id));
+    }
+    if !take_unallocated_sheets(&mut sheets).is_empty() {
+        return Err(format!("Seats have not been allocated for {}", transaction_id));
+    }
+    let pdf = render_exam_sheets_pdf(&format!("Exam Sheets {}", transaction_id), &sheets)?;
 
-    let params = match room_number {
-        Some(room) => params! { "selected_date" => selected_date, "room_number" => room },
-        None => params! { "selected_date" => selected_date },
+    let Some(path) = FileDialogBuilder::new()
+        .add_filter("PDF", &["pdf"])
+        .set_file_name(&format!("{}.pdf", safe_file_name(&transaction_id)))
+        .save_file()
+    else {
+        return Ok(None);
     };
-    
-    let result: Vec<(String, String)> = conn.exec(query, params)
-        .map_err(|e| format!("Failed to execute query: {}", e))?;
-    
-    let room_transactions = result.into_iter()
-        .map(|(room_number, shift_id)| RoomTransaction {
-            room_number,
-            shift_id,
+
+    std::fs::write(&path, pdf).map_err(|e| format!("Failed to write PDF: {}", e))?;
+    Ok(Some(path.to_string_lossy().into_owned()))
+}
+
+#[derive(Debug, Serialize)]
+pub struct DaySheetExport {
+    pub written: Vec<String>,
+    // Exams of the day left out because their seats have not been allocated yet.
+    pub unallocated: Vec<String>,
+}
+
+/// Writes one PDF per room into a folder chosen by the user, covering every exam on `date` (YYYY-MM-DD)
+/// that has its seats allocated. The exams still waiting for seats are reported instead.
+#[tauri::command]
+async fn export_exam_day_sheets(
+    date: String,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<Option<DaySheetExport>, String> {
+    require_role(&current_user, &[EXAM_COORDINATOR, ASSISTANT])?;
+
+    let mut sheets = {
+        let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+        load_exam_sheets(&mut conn, "et.transaction_date = :date", params! { "date" => &date })?
+    };
+    if sheets.is_empty() {
+        return Err(format!("No exams scheduled on {}", date));
+    }
+    let unallocated = take_unallocated_sheets(&mut sheets);
+    if sheets.is_empty() {
+        return Err(format!("Seats have not been allocated for: {}", unallocated.join(", ")));
+    }
+
+    let mut rooms: BTreeMap<String, Vec<ExamSheet>> = BTreeMap::new();
+    for sheet in sheets {
+        rooms.entry(sheet.room_number.clone()).or_default().push(sheet);
+    }
+
+    let Some(folder) = FileDialogBuilder::new().pick_folder() else {
+        return Ok(None);
+    };
+
+    let mut written = Vec::new();
+    for (room_number, sheets) in &rooms {
+        let pdf = render_exam_sheets_pdf(&format!("Exam Sheets {} {}", date, room_number), sheets)?;
+        let path = folder.join(format!("{}-{}.pdf", safe_file_name(&date), safe_file_name(room_number)));
+        std::fs::write(&path, pdf).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        written.push(path.to_string_lossy().into_owned());
+    }
+
+    Ok(Some(DaySheetExport { written, unallocated }))
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportRowError {
+    pub row: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScheduleImportReport {
+    pub total_rows: usize,
+    pub imported: Vec<String>,
+    pub errors: Vec<ImportRowError>,
+    pub warnings: Vec<TravelWarning>,
+    pub committed: bool,
+}
+
+// One data row keyed by normalized header, with its 1-based line/row number in the source file.
+struct TabularRecord {
+    row: usize,
+    fields: HashMap<String, String>,
+}
+
+impl TabularRecord {
+    // The first non-empty value among `names`, so headers like "room" and "room_number" both work.
+    fn get(&self, names: &[&str]) -> Option<String> {
+        names.iter()
+            .filter_map(|name| self.fields.get(*name))
+            .map(|value| value.trim())
+            .find(|value| !value.is_empty())
+            .map(|value| value.to_string())
+    }
+}
+
+fn normalize_header(header: &str) -> String {
+    header.trim().to_lowercase().replace([' ', '-'], "_")
+}
+
+fn spreadsheet_cell_text(cell: &calamine::Data) -> String {
+    match cell {
+        calamine::Data::Empty => String::new(),
+        // Numeric room numbers and shift ids come back as floats.
+        calamine::Data::Float(value) if value.fract() == 0.0 => format!("{}", *value as i64),
+        calamine::Data::DateTime(_) | calamine::Data::DateTimeIso(_) => cell.as_date()
+            .map(|date| date.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| cell.to_string()),
+        other => other.to_string(),
+    }
+}
+
+/// Reads the first sheet of an XLSX/XLS/ODS workbook, or a CSV file, into header-keyed records.
+fn read_tabular_file(path: &std::path::Path) -> Result<Vec<TabularRecord>, String> {
+    let extension = path.extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| extension.to_lowercase())
+        .unwrap_or_default();
+
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    if extension == "csv" {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .from_path(path)
+            .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        for record in reader.records() {
+            let record = record.map_err(|e| format!("Failed to read CSV: {}", e))?;
+            rows.push(record.iter().map(|value| value.to_string()).collect());
+        }
+    } else {
+        let mut workbook = open_workbook_auto(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        let range = workbook.worksheet_range_at(0)
+            .ok_or_else(|| "Workbook has no sheets".to_string())?
+            .map_err(|e| format!("Failed to read sheet: {}", e))?;
+        rows.extend(range.rows().map(|row| row.iter().map(spreadsheet_cell_text).collect()));
+    }
+
+    let mut rows = rows.into_iter().enumerate();
+    let Some((_, headers)) = rows.next() else {
+        return Ok(Vec::new());
+    };
+    let headers: Vec<String> = headers.iter().map(|header| normalize_header(header)).collect();
+
+    Ok(rows
+        .filter(|(_, values)| values.iter().any(|value| !value.trim().is_empty()))
+        .map(|(index, values)| TabularRecord {
+            row: index + 1,
+            fields: headers.iter().cloned().zip(values).collect(),
         })
-        .collect();
-    
-    Ok(room_transactions)
+        .collect())
+}
+
+#[derive(Debug)]
+struct ScheduleRow {
+    row: usize,
+    subject_code: String,
+    class_code: Option<String>,
+    room_number: String,
+    transaction_date: String,
+    shift_id: String,
+    proctor: Option<String>,
+    period_id: u32,
+}
+
+struct ScheduleLookups {
+    subjects: HashSet<String>,
+    // room_number -> campus
+    rooms: HashMap<String, String>,
+    // shift_id -> (start_time, end_time), active shifts only
+    shifts: HashMap<String, (String, String)>,
+    // class_code -> subject_code
+    classes: HashMap<String, String>,
+    // bn_number or initial -> bn_number, active assistants only
+    assistants: HashMap<String, String>,
+    // (date, shift_id, room_number)
+    booked_rooms: HashSet<(String, String, String)>,
+    // (date, shift_id, proctor bn_number)
+    booked_proctors: HashSet<(String, String, String)>,
+    transaction_ids: HashSet<String>,
+    periods: Vec<OpenPeriod>,
+    blackouts: Vec<BlackoutDate>,
+    unavailability: Vec<RoomUnavailability>,
+}
+
+fn load_schedule_lookups(conn: &mut PooledConn) -> Result<ScheduleLookups, String> {
+    let subjects: Vec<String> = conn.query("SELECT subject_code FROM subject")
+        .map_err(|e| format!("Failed to fetch subjects: {}", e))?;
+    let rooms: Vec<(String, String)> = conn.query("SELECT room_number, campus FROM room")
+        .map_err(|e| format!("Failed to fetch rooms: {}", e))?;
+    let shifts: Vec<(String, String, String)> = conn.query(
+        "SELECT shift_id, TIME_FORMAT(start_time, '%H:%i:%s'), TIME_FORMAT(end_time, '%H:%i:%s') FROM shift WHERE retired = FALSE",
+    ).map_err(|e| format!("Failed to fetch shifts: {}", e))?;
+    let classes = conn.query_map("SELECT DISTINCT class_code, subject_code FROM enrollment", |row: (String, String)| row)
+        .map_err(|e| format!("Failed to fetch classes: {}", e))?;
+
+    let mut assistants = HashMap::new();
+    conn.exec_map(
+        format!(
+            r"SELECT users.bn_number, users.initial FROM users
+            JOIN user_roles ON user_roles.bn_number = users.bn_number
+            WHERE user_roles.role_name = :role_name AND {}",
+            ACTIVE_GRANT_CONDITION
+        ),
+        params! { "role_name" => ASSISTANT },
+        |(bn_number, initial): (String, Option<String>)| (bn_number, initial),
+    ).map_err(|e| format!("Failed to fetch assistants: {}", e))?
+        .into_iter()
+        .for_each(|(bn_number, initial)| {
+            if let Some(initial) = initial.filter(|initial| !initial.is_empty()) {
+                assistants.insert(initial, bn_number.clone());
+            }
+            assistants.insert(bn_number.clone(), bn_number);
+        });
+
+    let mut booked_rooms = HashSet::new();
+    let mut booked_proctors = HashSet::new();
+    let mut transaction_ids = HashSet::new();
+    let bookings: Vec<(String, String, String, String, Option<String>)> = conn.query(
+        r"SELECT transaction_id, DATE_FORMAT(transaction_date, '%Y-%m-%d'), shift_id, room_number, proctor
+        FROM exam_transaction",
+    ).map_err(|e| format!("Failed to fetch exam transactions: {}", e))?;
+    for (transaction_id, date, shift_id, room_number, proctor) in bookings {
+        if let Some(proctor) = proctor {
+            let proctor = assistants.get(&proctor).cloned().unwrap_or(proctor);
+            booked_proctors.insert((date.clone(), shift_id.clone(), proctor));
+        }
+        booked_rooms.insert((date, shift_id, room_number));
+        transaction_ids.insert(transaction_id);
+    }
+
+    Ok(ScheduleLookups {
+        subjects: subjects.into_iter().collect(),
+        rooms: rooms.into_iter().collect(),
+        shifts: shifts.into_iter().map(|(shift_id, start_time, end_time)| (shift_id, (start_time, end_time))).collect(),
+        classes: classes.into_iter().collect(),
+        assistants,
+        booked_rooms,
+        booked_proctors,
+        transaction_ids,
+        periods: load_open_periods(conn)?,
+        blackouts: load_blackout_dates(conn, None, None)?,
+        unavailability: load_room_unavailability(conn, None, None)?,
+    })
+}
+
+// Validates one record, reserving its room and proctor slot so later rows in the file conflict with it.
+fn validate_schedule_row(
+    record: &TabularRecord,
+    period_id: Option<u32>,
+    lookups: &mut ScheduleLookups,
+) -> Result<ScheduleRow, Vec<String>> {
+    let mut problems = Vec::new();
+
+    let subject_code = record.get(&["subject_code", "subject"]).unwrap_or_default();
+    let class_code = record.get(&["class", "class_code"]);
+    let room_number = record.get(&["room", "room_number"]).unwrap_or_default();
+    let date = record.get(&["date", "transaction_date"]).unwrap_or_default();
+    let shift_id = record.get(&["shift", "shift_id"]).unwrap_or_default();
+    let proctor = record.get(&["proctor"]);
+
+    if subject_code.is_empty() {
+        problems.push("subject_code is required".to_string());
+    } else if !lookups.subjects.contains(&subject_code) {
+        problems.push(format!("Unknown subject {}", subject_code));
+    }
+
+    if let Some(class_code) = &class_code {
+        match lookups.classes.get(class_code) {
+            None => problems.push(format!("Unknown class {}", class_code)),
+            Some(class_subject) if !subject_code.is_empty() && *class_subject != subject_code => {
+                problems.push(format!("Class {} belongs to subject {}", class_code, class_subject));
+            }
+            Some(_) => {}
+        }
+    }
+
+    if room_number.is_empty() {
+        problems.push("room is required".to_string());
+    } else if !lookups.rooms.contains_key(&room_number) {
+        problems.push(format!("Unknown room {}", room_number));
+    }
+
+    if shift_id.is_empty() {
+        problems.push("shift is required".to_string());
+    } else if !lookups.shifts.contains_key(&shift_id) {
+        problems.push(format!("Unknown shift {}", shift_id));
+    }
+
+    let parsed_date = chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d").ok();
+    let transaction_date = match parsed_date {
+        Some(parsed) => parsed.format("%Y-%m-%d").to_string(),
+        None => {
+            problems.push(format!("Invalid date '{}', expected YYYY-MM-DD", date));
+            String::new()
+        }
+    };
+
+    if let Some(parsed) = parsed_date {
+        let campus = lookups.rooms.get(&room_number).map(String::as_str);
+        if let Some(reason) = blackout_reason(parsed, campus, &lookups.blackouts) {
+            problems.push(format!("{} is closed for exams: {}", transaction_date, reason));
+        }
+    }
+
+    let period_id = if transaction_date.is_empty() {
+        0
+    } else {
+        pick_exam_period(&lookups.periods, period_id, &transaction_date).unwrap_or_else(|problem| {
+            problems.push(problem);
+            0
+        })
+    };
+
+    let proctor = match proctor {
+        Some(proctor) => match lookups.assistants.get(&proctor) {
+            Some(bn_number) => Some(bn_number.clone()),
+            None => {
+                problems.push(format!("Proctor {} is not an active assistant", proctor));
+                None
+            }
+        },
+        None => None,
+    };
+
+    if !problems.is_empty() {
+        return Err(problems);
+    }
+
+    let (start_time, end_time) = &lookups.shifts[&shift_id];
+    let start_at = format!("{} {}", transaction_date, start_time);
+    let end_at = format!("{} {}", transaction_date, end_time);
+    if let Some(window) = unavailability_overlapping(&lookups.unavailability, &room_number, &start_at, &end_at) {
+        problems.push(format!("Room {} is unavailable from {} to {}: {}", room_number, window.start_at, window.end_at, window.reason));
+    }
+
+    let room_slot = (transaction_date.clone(), shift_id.clone(), room_number.clone());
+    if !lookups.booked_rooms.insert(room_slot) {
+        problems.push(format!("Room {} is already booked on {} shift {}", room_number, transaction_date, shift_id));
+    }
+    if let Some(proctor) = &proctor {
+        let proctor_slot = (transaction_date.clone(), shift_id.clone(), proctor.clone());
+        if !lookups.booked_proctors.insert(proctor_slot) {
+            problems.push(format!("Proctor {} is already assigned on {} shift {}", proctor, transaction_date, shift_id));
+        }
+    }
+
+    if !problems.is_empty() {
+        return Err(problems);
+    }
+
+    Ok(ScheduleRow {
+        row: record.row,
+        subject_code,
+        class_code,
+        room_number,
+        transaction_date,
+        shift_id,
+        proctor,
+        period_id,
+    })
 }
 
+fn next_transaction_id(taken: &mut HashSet<String>) -> Result<String, String> {
+    let mut rng = rand::thread_rng();
+    for _ in 0..1000 {
+        let transaction_id = format!("TI{:04}", rng.gen_range(0..10000));
+        if taken.insert(transaction_id.clone()) {
+            return Ok(transaction_id);
+        }
+    }
+    Err("No free transaction id available".to_string())
+}
+
+/// Validates every row of a timetable file and, unless `dry_run` is set, inserts them all in one
+/// transaction. A single invalid row means nothing is imported. Without a `path` the user picks the file.
 #[tauri::command]
-async fn get_exam_transaction(mysql_pool: State<'_, Pool>) -> Result<Vec<ExamTransaction>, String> {
+async fn import_exam_schedule(
+    path: Option<String>,
+    period_id: Option<u32>,
+    dry_run: bool,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<Option<ScheduleImportReport>, String> {
+    let coordinator = require_role(&current_user, &[EXAM_COORDINATOR])?;
+
+    let path = match path {
+        Some(path) => std::path::PathBuf::from(path),
+        None => {
+            let Some(path) = FileDialogBuilder::new()
+                .add_filter("Spreadsheet", &["csv", "xlsx", "xls", "ods"])
+                .pick_file()
+            else {
+                return Ok(None);
+            };
+            path
+        }
+    };
+
+    let records = read_tabular_file(&path)?;
     let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+    let mut lookups = load_schedule_lookups(&mut conn)?;
 
-    let transactions: Vec<ExamTransaction> = conn.query_map(
-        "SELECT transaction_id, subject_code, room_number, shift_id, transaction_date, proctor, status FROM exam_transaction",
-        |(transaction_id, subject_code, room_number, shift_id, transaction_date, proctor, status)| {
-            ExamTransaction {
-                transaction_id,
-                subject_code,
-                room_number,
-                shift_id,
-                transaction_date,
-                proctor,
-                status,
+    let mut rows = Vec::new();
+    let mut errors = Vec::new();
+    for record in &records {
+        match validate_schedule_row(record, period_id, &mut lookups) {
+            Ok(row) => rows.push(row),
+            Err(problems) => errors.extend(problems.into_iter().map(|message| ImportRowError { row: record.row, message })),
+        }
+    }
+
+    let mut report = ScheduleImportReport {
+        total_rows: records.len(),
+        imported: Vec::new(),
+        errors,
+        warnings: Vec::new(),
+        committed: false,
+    };
+
+    let proctored: Vec<&ScheduleRow> = rows.iter().filter(|row| row.proctor.is_some()).collect();
+    if let (Some(first), Some(last)) = (
+        proctored.iter().map(|row| row.transaction_date.as_str()).min(),
+        proctored.iter().map(|row| row.transaction_date.as_str()).max(),
+    ) {
+        let mut slots = load_proctor_slots(
+            &mut conn,
+            "et.transaction_date BETWEEN :date_from AND :date_to",
+            params! { "date_from" => first, "date_to" => last },
+        )?;
+        slots.extend(proctored.iter().map(|row| {
+            let (start_time, end_time) = &lookups.shifts[&row.shift_id];
+            ProctorSlot {
+                proctor: row.proctor.clone().unwrap_or_default(),
+                exam: format!("row {}", row.row),
+                date: row.transaction_date.clone(),
+                start_time: start_time.clone(),
+                end_time: end_time.clone(),
+                campus: lookups.rooms[&row.room_number].clone(),
             }
+        }));
+        report.warnings = travel_warnings(slots, &load_travel_times(&mut conn)?)
+            .into_iter()
+            .filter(|warning| warning.from_exam.starts_with("row ") || warning.to_exam.starts_with("row "))
+            .collect();
+    }
+
+    if dry_run || !report.errors.is_empty() || rows.is_empty() {
+        return Ok(Some(report));
+    }
+
+    let mut transaction = conn.start_transaction(TxOpts::default())
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    for row in &rows {
+        let transaction_id = next_transaction_id(&mut lookups.transaction_ids)?;
+        transaction.exec_drop(
+            r"INSERT INTO exam_transaction (
+                transaction_id, subject_code, class_code, room_number, shift_id, transaction_date, proctor, period_id
+            ) VALUES (
+                :transaction_id, :subject_code, :class_code, :room_number, :shift_id, :transaction_date, :proctor, :period_id
+            )",
+            params! {
+                "transaction_id" => &transaction_id,
+                "subject_code" => &row.subject_code,
+                "class_code" => &row.class_code,
+                "room_number" => &row.room_number,
+                "shift_id" => &row.shift_id,
+                "transaction_date" => &row.transaction_date,
+                "proctor" => &row.proctor,
+                "period_id" => row.period_id,
+            },
+        ).map_err(|e| format!("Failed to import row {}: {}", row.row, e))?;
+        report.imported.push(transaction_id);
+    }
+
+    record_audit(
+        &mut transaction,
+        coordinator.bn_number.inner(),
+        "exam_schedule_imported",
+        "exam_transaction",
+        &format!("{} rows from {}", report.imported.len(), path.display()),
+    )?;
+
+    transaction.commit()
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+    report.committed = true;
+
+    Ok(Some(report))
+}
+
+#[derive(Debug, Serialize)]
+pub struct MasterDataImportReport {
+    pub dataset: String,
+    pub imported: usize,
+}
+
+// Accepts a bare array of records or a saved GraphQL response such as {"data": {"getAllUser": [...]}}.
+fn json_records<T: serde::de::DeserializeOwned>(contents: &str) -> Result<Vec<T>, String> {
+    let value: serde_json::Value = serde_json::from_str(contents).map_err(|e| format!("Invalid JSON: {}", e))?;
+    let payload = value.get("data").cloned().unwrap_or(value);
+
+    let records = match payload {
+        serde_json::Value::Array(records) => records,
+        serde_json::Value::Object(fields) => fields.into_iter()
+            .find_map(|(_, field)| match field {
+                serde_json::Value::Array(records) => Some(records),
+                _ => None,
+            })
+            .ok_or_else(|| "JSON object contains no list of records".to_string())?,
+        _ => return Err("Expected a JSON array of records".to_string()),
+    };
+
+    records.into_iter()
+        .filter(|record| !record.is_null())
+        .enumerate()
+        .map(|(index, record)| serde_json::from_value(record).map_err(|e| format!("Invalid record {}: {}", index + 1, e)))
+        .collect()
+}
+
+fn csv_records<T: serde::de::DeserializeOwned>(path: &std::path::Path) -> Result<Vec<T>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_path(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+
+    reader.deserialize()
+        .enumerate()
+        .map(|(index, record)| record.map_err(|e| format!("Invalid row {}: {}", index + 2, e)))
+        .collect()
+}
+
+fn read_master_data<T: serde::de::DeserializeOwned>(path: &std::path::Path) -> Result<Vec<T>, String> {
+    let is_csv = path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("csv"));
+
+    if is_csv {
+        csv_records(path)
+    } else {
+        let contents = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        json_records(&contents)
+    }
+}
+
+/// Loads users, rooms, subjects or enrollments from a JSON/CSV dump for machines without access
+/// to the GraphQL service. Importing users into an empty users table needs no login, so a fresh
+/// install can be bootstrapped; every other import requires a coordinator.
+#[tauri::command]
+async fn import_master_data(
+    dataset: Dataset,
+    path: Option<String>,
+    mysql_pool: State<'_, Pool>,
+    current_user: State<'_, AppState>,
+) -> Result<Option<MasterDataImportReport>, String> {
+    let mut conn = mysql_pool.get_conn().map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    let bootstrap = match dataset {
+        Dataset::Users => {
+            let user_count: Option<u64> = conn.query_first("SELECT COUNT(*) FROM users")
+                .map_err(|e| format!("Failed to count users: {}", e))?;
+            user_count.unwrap_or(0) == 0
         }
-    ).map_err(|e| format!("Failed to execute query: {}", e))?;    
+        _ => false,
+    };
+    let actor = if bootstrap {
+        "bootstrap".to_string()
+    } else {
+        require_role(&current_user, &[EXAM_COORDINATOR])?.bn_number.into_inner()
+    };
 
-    Ok(transactions)
+    let path = match path {
+        Some(path) => std::path::PathBuf::from(path),
+        None => {
+            let Some(path) = FileDialogBuilder::new()
+                .add_filter("Data dump", &["json", "csv"])
+                .pick_file()
+            else {
+                return Ok(None);
+            };
+            path
+        }
+    };
+
+    let mut transaction = conn.start_transaction(TxOpts::default())
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let imported = match dataset {
+        Dataset::Users => {
+            let users: Vec<User> = read_master_data(&path)?;
+            let count = users.len();
+            store_users(&mut transaction, users)?;
+            count
+        }
+        Dataset::Rooms => {
+            let rooms: Vec<Room> = read_master_data(&path)?;
+            let count = rooms.len();
+            store_rooms(&mut transaction, rooms)?;
+            count
+        }
+        Dataset::Subjects => {
+            let subjects: Vec<Subject> = read_master_data(&path)?;
+            let count = subjects.len();
+            store_subjects(&mut transaction, subjects)?;
+            count
+        }
+        Dataset::Enrollments => {
+            let enrollments: Vec<Enrollment> = read_master_data(&path)?;
+            let count = enrollments.len();
+            store_enrollments(&mut transaction, enrollments)?;
+            count
+        }
+    };
+
+    if let Dataset::Users = dataset {
+        backfill_user_roles(&mut transaction).map_err(|e| format!("Failed to backfill user roles: {}", e))?;
+    }
+
+    record_audit(
+        &mut transaction,
+        &actor,
+        "master_data_imported",
+        dataset.name(),
+        &format!("{} rows from {}", imported, path.display()),
+    )?;
+
+    // Imported rows are not marked as synced, so the next online start still refreshes them from the remote.
+    transaction.commit()
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    Ok(Some(MasterDataImportReport {
+        dataset: dataset.name().to_string(),
+        imported,
+    }))
 }
 
 #[tauri::command]
-async fn insert_users(conn: &mut PooledConn) -> Result<(), ()> {
-    let users = get_all_users().await?;
+async fn insert_users(conn: &mut PooledConn, client: &GraphQlClient) -> Result<(), String> {
+    let users = fetch_all_users(client).await?;
+    store_users(conn, users)
+}
 
+// Upserts rows shaped like the GraphQL fragment, whether fetched remotely or read from a dump file.
+fn store_users(conn: &mut impl Queryable, users: Vec<User>) -> Result<(), String> {
     for user in users {
         // println!("Inserting user: {:?}", user);
         let bn_number = user.bn_number.clone();
@@ -453,7 +6383,7 @@ async fn insert_users(conn: &mut PooledConn) -> Result<(), ()> {
             params! {
                 "bn_number" => bn_number.clone().into_inner(),
             },
-        ).expect("Failed to check if user exists");
+        ).map_err(|e| format!("Failed to check if user exists: {}", e))?;
 
         if user_exists.is_none() {
             conn.exec_drop(
@@ -468,18 +6398,32 @@ async fn insert_users(conn: &mut PooledConn) -> Result<(), ()> {
                     "role" => user.role,
                     "initial" => user.initial.unwrap_or_default(),
                 },
-            ).expect("Failed to insert");
+            ).map_err(|e| format!("Failed to insert user: {}", e))?;
         } else {
-            // println!("User already exists: {:?}", user);
+            // The local role is managed here (see reconcile commands), so only profile fields follow the remote.
+            conn.exec_drop(
+                r"UPDATE users SET nim = :nim, name = :name, major = :major, initial = :initial
+                WHERE bn_number = :bn_number",
+                params! {
+                    "bn_number" => bn_number.into_inner(),
+                    "nim" => user.nim,
+                    "name" => user.name,
+                    "major" => user.major,
+                    "initial" => user.initial.unwrap_or_default(),
+                },
+            ).map_err(|e| format!("Failed to update user: {}", e))?;
         }
     }
     Ok(())
 }
 
 #[tauri::command]
-async fn insert_room(conn: &mut PooledConn) -> Result<(), String> {
-    let rooms = get_all_room().await.map_err(|_| "Failed to fetch rooms".to_string())?;
+async fn insert_room(conn: &mut PooledConn, client: &GraphQlClient) -> Result<(), String> {
+    let rooms = fetch_all_rooms(client).await.map_err(|e| format!("Failed to fetch rooms: {}", e))?;
+    store_rooms(conn, rooms)
+}
 
+fn store_rooms(conn: &mut impl Queryable, rooms: Vec<Room>) -> Result<(), String> {
     for room in rooms {
         let room_num_str = room.room_number_str.clone();
 
@@ -492,52 +6436,58 @@ async fn insert_room(conn: &mut PooledConn) -> Result<(), String> {
 
         if room_exists.is_none() {
             conn.exec_drop(
-                r"INSERT INTO room (room_number, room_capacity, campus)
-                VALUES (:room_number, :room_capacity, :campus)",
+                r"INSERT INTO room (room_number, room_capacity, campus)
+                VALUES (:room_number, :room_capacity, :campus)",
+                params! {
+                    "room_number" => room.room_number_str,
+                    "room_capacity" => room.room_capacity,
+                    "campus" => room.campus,
+                },
+            ).map_err(|e| format!("Failed to insert room: {}", e))?;
+        } else {
+            conn.exec_drop(
+                r"UPDATE room SET room_capacity = :room_capacity, campus = :campus WHERE room_number = :room_number",
                 params! {
                     "room_number" => room.room_number_str,
                     "room_capacity" => room.room_capacity,
                     "campus" => room.campus,
                 },
-            ).map_err(|e| format!("Failed to insert room: {}", e))?;
+            ).map_err(|e| format!("Failed to update room: {}", e))?;
         }
     }
     Ok(())
 }
 
 #[tauri::command]
-async fn insert_enrollment(conn: &mut PooledConn) -> Result<(), String> {
-    let enrollments = get_all_enrollment().await.map_err(|_| "Failed to fetch enroolment".to_string())?;
+async fn insert_enrollment(conn: &mut PooledConn, client: &GraphQlClient) -> Result<(), String> {
+    let enrollments = fetch_all_enrollments(client).await.map_err(|e| format!("Failed to fetch enrollment: {}", e))?;
+    store_enrollments(conn, enrollments)
+}
 
+fn store_enrollments(conn: &mut impl Queryable, enrollments: Vec<Enrollment>) -> Result<(), String> {
+    // A class holds many students, so each enrollment is keyed by the (class_code, nim) pair.
     for enrollment in enrollments {
-        let class_code_str = enrollment.class_code_str.clone();
-
-        let enrollment_exists: Option<String> = conn.exec_first(
-            r"SELECT class_code FROM enrollment WHERE class_code = :class_code", 
+        conn.exec_drop(
+            r"INSERT INTO enrollment (class_code, nim, subject_code)
+            VALUES (:class_code, :nim, :subject_code)
+            ON DUPLICATE KEY UPDATE subject_code = VALUES(subject_code)",
             params! {
-                "class_code" => class_code_str.clone(),
+                "class_code" => enrollment.class_code_str,
+                "nim" => enrollment.nim,
+                "subject_code" => enrollment.subject_code,
             },
-        ).map_err(|e| format!("Failed to check if enrollment exists: {}", e))?;
-
-        if enrollment_exists.is_none(){
-            conn.exec_drop(
-                r"INSERT INTO enrollment (class_code, nim, subject_code)
-                VALUES (:class_code, :nim, :subject_code)", 
-                params! {
-                    "class_code" => enrollment.class_code_str,
-                    "nim" => enrollment.nim,
-                    "subject_code" => enrollment.subject_code,
-                },
-            ).map_err(|e| format!("Failed to insert enrollment: {}", e))?;
-        }
+        ).map_err(|e| format!("Failed to store enrollment: {}", e))?;
     }
     Ok(())
 }
 
 #[tauri::command]
-async fn insert_subject(conn: &mut PooledConn) -> Result<(), String> {
-    let subjects = get_all_subject().await.map_err(|_| "Failed to fetch subject".to_string())?;
+async fn insert_subject(conn: &mut PooledConn, client: &GraphQlClient) -> Result<(), String> {
+    let subjects = fetch_all_subjects(client).await.map_err(|e| format!("Failed to fetch subject: {}", e))?;
+    store_subjects(conn, subjects)
+}
 
+fn store_subjects(conn: &mut impl Queryable, subjects: Vec<Subject>) -> Result<(), String> {
     for subject in subjects {
         let subject_code_str = subject.subject_code_str.clone();
 
@@ -557,6 +6507,14 @@ async fn insert_subject(conn: &mut PooledConn) -> Result<(), String> {
                     "subject_name" => subject.subject_name,
                 },
             ).map_err(|e| format!("failet to insert subject : {}", e))?;
+        } else {
+            conn.exec_drop(
+                r"UPDATE subject SET subject_name = :subject_name WHERE subject_code = :subject_code",
+                params! {
+                    "subject_code" => subject.subject_code_str,
+                    "subject_name" => subject.subject_name,
+                },
+            ).map_err(|e| format!("Failed to update subject: {}", e))?;
         }
     }
     Ok(())
@@ -570,10 +6528,16 @@ async fn insert_exam_transaction(
     room_number_str: String,
     shift_id: String,
     transaction_date: String,
+    period_id: Option<u32>,
 ) -> Result<(), String> {
     let mut conn = mysql_pool.get_conn()
         .map_err(|e| format!("Failed to get connection: {}", e))?;
 
+    let periods = load_open_periods(&mut conn)?;
+    let period_id = pick_exam_period(&periods, period_id, &transaction_date)?;
+    ensure_date_schedulable(&mut conn, &transaction_date, &room_number_str)?;
+    ensure_room_available(&mut conn, &room_number_str, &transaction_date, &shift_id)?;
+
     let mut transaction = conn.start_transaction(TxOpts::default())
         .map_err(|e| format!("Failed to start transaction: {}", e))?;
 
@@ -583,10 +6547,10 @@ async fn insert_exam_transaction(
     transaction.exec_drop(
         r"INSERT INTO exam_transaction (
             transaction_id, subject_code, room_number, shift_id, 
-            transaction_date
+            transaction_date, period_id
         ) VALUES (
             :transaction_id, :subject_code, :room_number, :shift_id, 
-            :transaction_date
+            :transaction_date, :period_id
         )",
         params! {
             "transaction_id" => &transaction_id,
@@ -594,6 +6558,7 @@ async fn insert_exam_transaction(
             "room_number" => &room_number_str,
             "shift_id" => &shift_id,
             "transaction_date" => &transaction_date,
+            "period_id" => period_id,
         },
     ).map_err(|e| format!("Failed to execute query: {}", e))?;
 
@@ -621,14 +6586,49 @@ fn create_users_table_if_not_exists(conn: &mut PooledConn) -> Result<(), mysql::
 fn create_shift_table_if_not_exists(conn: &mut PooledConn) -> Result<(), mysql::Error> {
     conn.exec_drop(
         r"CREATE TABLE IF NOT EXISTS shift (
-            shift_id VARCHAR(1) PRIMARY KEY,
+            shift_id VARCHAR(10) PRIMARY KEY,
             start_time TIME NOT NULL,
-            end_time TIME NOT NULL
+            end_time TIME NOT NULL,
+            retired BOOLEAN NOT NULL DEFAULT FALSE
         )",
         ()
     )
 }
 
+// Older installs created shift ids as VARCHAR(1), which caps the table at 36 shifts.
+fn widen_shift_id_if_needed(conn: &mut PooledConn) -> Result<(), mysql::Error> {
+    let length: Option<u64> = conn.query_first(
+        r"SELECT CHARACTER_MAXIMUM_LENGTH FROM information_schema.COLUMNS
+        WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = 'shift' AND COLUMN_NAME = 'shift_id'",
+    )?;
+
+    if length.unwrap_or(0) < SHIFT_ID_MAX_LENGTH as u64 {
+        // Both ends of the foreign key have to change together.
+        conn.query_drop("SET FOREIGN_KEY_CHECKS = 0")?;
+        let widened = conn.query_drop("ALTER TABLE shift MODIFY shift_id VARCHAR(10) NOT NULL")
+            .and_then(|_| conn.query_drop("ALTER TABLE exam_transaction MODIFY shift_id VARCHAR(10) NOT NULL"));
+        conn.query_drop("SET FOREIGN_KEY_CHECKS = 1")?;
+        widened?;
+    }
+    Ok(())
+}
+
+fn seed_default_shifts(conn: &mut PooledConn) -> Result<(), mysql::Error> {
+    let count: Option<u64> = conn.query_first("SELECT COUNT(*) FROM shift")?;
+    if count.unwrap_or(0) > 0 {
+        return Ok(());
+    }
+
+    conn.exec_batch(
+        "INSERT INTO shift (shift_id, start_time, end_time) VALUES (:shift_id, :start_time, :end_time)",
+        DEFAULT_SHIFTS.iter().map(|&(shift_id, start_time, end_time)| params! {
+            "shift_id" => shift_id,
+            "start_time" => start_time,
+            "end_time" => end_time,
+        }),
+    )
+}
+
 fn create_room_table_if_not_exists(conn: &mut PooledConn) -> Result<(), mysql::Error> {
     conn.exec_drop(
         r"CREATE TABLE IF NOT EXISTS room (
@@ -643,9 +6643,10 @@ fn create_room_table_if_not_exists(conn: &mut PooledConn) -> Result<(), mysql::E
 fn create_enrollment_table_if_not_exists(conn: &mut PooledConn) -> Result<(), mysql::Error> {
     conn.exec_drop(
         r"CREATE TABLE IF NOT EXISTS enrollment (
-            class_code VARCHAR(255) PRIMARY KEY,
+            class_code VARCHAR(255) NOT NULL,
             nim VARCHAR(255) NOT NULL,
             subject_code VARCHAR(255) NOT NULL,
+            PRIMARY KEY (class_code, nim),
             FOREIGN KEY (nim) REFERENCES users(nim),
             FOREIGN KEY (subject_code) REFERENCES subject(subject_code)
         )",
@@ -653,6 +6654,19 @@ fn create_enrollment_table_if_not_exists(conn: &mut PooledConn) -> Result<(), my
     )
 }
 
+// Older installs keyed enrollment by class_code alone, which left room for one student per class.
+fn widen_enrollment_key_if_needed(conn: &mut PooledConn) -> Result<(), mysql::Error> {
+    let key_columns: Option<u64> = conn.query_first(
+        r"SELECT COUNT(*) FROM information_schema.KEY_COLUMN_USAGE
+        WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = 'enrollment' AND CONSTRAINT_NAME = 'PRIMARY'",
+    )?;
+
+    if key_columns.unwrap_or(0) < 2 {
+        conn.query_drop("ALTER TABLE enrollment DROP PRIMARY KEY, ADD PRIMARY KEY (class_code, nim)")?;
+    }
+    Ok(())
+}
+
 fn create_subject_table_if_not_exists(conn: &mut PooledConn) -> Result<(), mysql::Error> {
     conn.exec_drop(
         r"CREATE TABLE IF NOT EXISTS subject (
@@ -669,7 +6683,7 @@ fn create_exam_transaction_if_not_exists(conn: &mut PooledConn) -> Result<(), my
             transaction_id VARCHAR(50) PRIMARY KEY,
             subject_code VARCHAR(50) NOT NULL,
             room_number VARCHAR(255) NOT NULL,
-            shift_id VARCHAR(1) NOT NULL,
+            shift_id VARCHAR(10) NOT NULL,
             transaction_date DATE NOT NULL,
             proctor VARCHAR(50),
             status VARCHAR(50),
@@ -681,6 +6695,358 @@ fn create_exam_transaction_if_not_exists(conn: &mut PooledConn) -> Result<(), my
     )
 }
 
+fn create_exam_period_table_if_not_exists(conn: &mut PooledConn) -> Result<(), mysql::Error> {
+    conn.exec_drop(
+        r"CREATE TABLE IF NOT EXISTS exam_period (
+            period_id INT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+            name VARCHAR(255) NOT NULL,
+            period_type VARCHAR(20) NOT NULL,
+            start_date DATE NOT NULL,
+            end_date DATE NOT NULL,
+            locked BOOLEAN NOT NULL DEFAULT FALSE,
+            archived BOOLEAN NOT NULL DEFAULT FALSE,
+            created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+        )",
+        ()
+    )
+}
+
+// Exams created before periods existed are bound to a covering period, or to one "Legacy" period spanning them.
+// The Legacy period is created locked so its old exams stay frozen and new exams are never assigned to it.
+fn bind_exam_transactions_to_periods(conn: &mut PooledConn) -> Result<(), mysql::Error> {
+    conn.query_drop(
+        r"UPDATE exam_transaction
+        JOIN exam_period ON exam_transaction.transaction_date BETWEEN exam_period.start_date AND exam_period.end_date
+        SET exam_transaction.period_id = exam_period.period_id
+        WHERE exam_transaction.period_id IS NULL",
+    )?;
+
+    let unbound: Option<(Option<String>, Option<String>)> = conn.query_first(
+        r"SELECT DATE_FORMAT(MIN(transaction_date), '%Y-%m-%d'), DATE_FORMAT(MAX(transaction_date), '%Y-%m-%d')
+        FROM exam_transaction WHERE period_id IS NULL",
+    )?;
+    let Some((Some(start_date), Some(end_date))) = unbound else {
+        return Ok(());
+    };
+
+    conn.exec_drop(
+        r"INSERT INTO exam_period (name, period_type, start_date, end_date, locked)
+        VALUES ('Legacy', :period_type, :start_date, :end_date, TRUE)",
+        params! { "period_type" => ExamPeriodType::Final.as_str(), "start_date" => start_date, "end_date" => end_date },
+    )?;
+    let period_id = conn.last_insert_id();
+    conn.exec_drop(
+        "UPDATE exam_transaction SET period_id = :period_id WHERE period_id IS NULL",
+        params! { "period_id" => period_id },
+    )
+}
+
+fn create_blackout_date_table_if_not_exists(conn: &mut PooledConn) -> Result<(), mysql::Error> {
+    conn.exec_drop(
+        r"CREATE TABLE IF NOT EXISTS blackout_date (
+            blackout_id INT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+            blackout_date DATE NOT NULL,
+            campus VARCHAR(255),
+            reason VARCHAR(255) NOT NULL,
+            INDEX (blackout_date)
+        )",
+        ()
+    )
+}
+
+fn create_room_attribute_table_if_not_exists(conn: &mut PooledConn) -> Result<(), mysql::Error> {
+    conn.exec_drop(
+        r"CREATE TABLE IF NOT EXISTS room_attribute (
+            room_number VARCHAR(255) PRIMARY KEY,
+            computer_count INT UNSIGNED,
+            broken_seats INT UNSIGNED NOT NULL DEFAULT 0,
+            accessible BOOLEAN NOT NULL DEFAULT FALSE,
+            has_projector BOOLEAN NOT NULL DEFAULT FALSE,
+            updated_by VARCHAR(255),
+            updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+            FOREIGN KEY (room_number) REFERENCES room(room_number)
+        )",
+        ()
+    )
+}
+
+fn create_room_unavailability_table_if_not_exists(conn: &mut PooledConn) -> Result<(), mysql::Error> {
+    conn.exec_drop(
+        r"CREATE TABLE IF NOT EXISTS room_unavailability (
+            unavailability_id INT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+            room_number VARCHAR(255) NOT NULL,
+            start_at DATETIME NOT NULL,
+            end_at DATETIME NOT NULL,
+            reason VARCHAR(255) NOT NULL,
+            created_by VARCHAR(255),
+            FOREIGN KEY (room_number) REFERENCES room(room_number),
+            INDEX (room_number, start_at)
+        )",
+        ()
+    )
+}
+
+fn create_campus_travel_table_if_not_exists(conn: &mut PooledConn) -> Result<(), mysql::Error> {
+    conn.exec_drop(
+        r"CREATE TABLE IF NOT EXISTS campus_travel (
+            campus_a VARCHAR(255) NOT NULL,
+            campus_b VARCHAR(255) NOT NULL,
+            minutes INT UNSIGNED NOT NULL,
+            PRIMARY KEY (campus_a, campus_b)
+        )",
+        ()
+    )
+}
+
+fn create_exam_reschedule_table_if_not_exists(conn: &mut PooledConn) -> Result<(), mysql::Error> {
+    conn.exec_drop(
+        r"CREATE TABLE IF NOT EXISTS exam_reschedule (
+            change_id INT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+            transaction_id VARCHAR(50) NOT NULL,
+            old_date DATE NOT NULL,
+            old_shift_id VARCHAR(10) NOT NULL,
+            old_room_number VARCHAR(255) NOT NULL,
+            new_date DATE NOT NULL,
+            new_shift_id VARCHAR(10) NOT NULL,
+            new_room_number VARCHAR(255) NOT NULL,
+            reason VARCHAR(255) NOT NULL,
+            changed_by VARCHAR(255) NOT NULL,
+            changed_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            FOREIGN KEY (transaction_id) REFERENCES exam_transaction(transaction_id),
+            INDEX (transaction_id, changed_at)
+        )",
+        ()
+    )
+}
+
+fn create_makeup_request_table_if_not_exists(conn: &mut PooledConn) -> Result<(), mysql::Error> {
+    conn.exec_drop(
+        r"CREATE TABLE IF NOT EXISTS makeup_request (
+            request_id INT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+            nim VARCHAR(255) NOT NULL,
+            subject_code VARCHAR(50) NOT NULL,
+            transaction_id VARCHAR(50) NOT NULL,
+            reason VARCHAR(255) NOT NULL,
+            status VARCHAR(20) NOT NULL DEFAULT 'pending',
+            requested_by VARCHAR(255) NOT NULL,
+            requested_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            reviewed_by VARCHAR(255),
+            reviewed_at TIMESTAMP NULL,
+            review_note VARCHAR(255),
+            makeup_transaction_id VARCHAR(50),
+            FOREIGN KEY (nim) REFERENCES users(nim),
+            FOREIGN KEY (transaction_id) REFERENCES exam_transaction(transaction_id),
+            FOREIGN KEY (makeup_transaction_id) REFERENCES exam_transaction(transaction_id),
+            INDEX (transaction_id, status)
+        )",
+        ()
+    )
+}
+
+fn add_column_if_missing(conn: &mut PooledConn, table: &str, column: &str, definition: &str) -> Result<(), mysql::Error> {
+    let exists: Option<u64> = conn.exec_first(
+        r"SELECT COUNT(*) FROM information_schema.COLUMNS
+        WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = :table_name AND COLUMN_NAME = :column_name",
+        params! { "table_name" => table, "column_name" => column },
+    )?;
+
+    if exists.unwrap_or(0) == 0 {
+        conn.query_drop(format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition))?;
+    }
+    Ok(())
+}
+
+fn create_seat_assignment_table_if_not_exists(conn: &mut PooledConn) -> Result<(), mysql::Error> {
+    conn.exec_drop(
+        r"CREATE TABLE IF NOT EXISTS seat_assignment (
+            transaction_id VARCHAR(50) NOT NULL,
+            nim VARCHAR(255) NOT NULL,
+            seat_number INT UNSIGNED NOT NULL,
+            PRIMARY KEY (transaction_id, nim),
+            UNIQUE (transaction_id, seat_number),
+            FOREIGN KEY (transaction_id) REFERENCES exam_transaction(transaction_id),
+            FOREIGN KEY (nim) REFERENCES users(nim)
+        )",
+        ()
+    )
+}
+
+fn create_password_policy_table_if_not_exists(conn: &mut PooledConn) -> Result<(), mysql::Error> {
+    conn.exec_drop(
+        r"CREATE TABLE IF NOT EXISTS password_policy (
+            id TINYINT PRIMARY KEY,
+            min_length INT UNSIGNED NOT NULL,
+            require_uppercase BOOLEAN NOT NULL,
+            require_lowercase BOOLEAN NOT NULL,
+            require_digit BOOLEAN NOT NULL,
+            require_symbol BOOLEAN NOT NULL,
+            history_size INT UNSIGNED NOT NULL,
+            forbid_personal_info BOOLEAN NOT NULL
+        )",
+        ()
+    )?;
+
+    let default_policy = PasswordPolicy::default();
+    conn.exec_drop(
+        r"INSERT IGNORE INTO password_policy (
+            id, min_length, require_uppercase, require_lowercase, require_digit,
+            require_symbol, history_size, forbid_personal_info
+        ) VALUES (
+            1, :min_length, :require_uppercase, :require_lowercase, :require_digit,
+            :require_symbol, :history_size, :forbid_personal_info
+        )",
+        params! {
+            "min_length" => default_policy.min_length,
+            "require_uppercase" => default_policy.require_uppercase,
+            "require_lowercase" => default_policy.require_lowercase,
+            "require_digit" => default_policy.require_digit,
+            "require_symbol" => default_policy.require_symbol,
+            "history_size" => default_policy.history_size,
+            "forbid_personal_info" => default_policy.forbid_personal_info,
+        },
+    )
+}
+
+fn create_password_history_table_if_not_exists(conn: &mut PooledConn) -> Result<(), mysql::Error> {
+    conn.exec_drop(
+        r"CREATE TABLE IF NOT EXISTS password_history (
+            id INT AUTO_INCREMENT PRIMARY KEY,
+            bn_number VARCHAR(255) NOT NULL,
+            password_hash VARCHAR(255) NOT NULL,
+            changed_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            FOREIGN KEY (bn_number) REFERENCES users(bn_number)
+        )",
+        ()
+    )
+}
+
+fn create_roles_table_if_not_exists(conn: &mut PooledConn) -> Result<(), mysql::Error> {
+    conn.exec_drop(
+        r"CREATE TABLE IF NOT EXISTS roles (
+            role_name VARCHAR(100) PRIMARY KEY
+        )",
+        ()
+    )?;
+
+    conn.exec_batch(
+        "INSERT IGNORE INTO roles (role_name) VALUES (:role_name)",
+        KNOWN_ROLES.iter().map(|role| params! { "role_name" => *role }),
+    )
+}
+
+fn create_user_roles_table_if_not_exists(conn: &mut PooledConn) -> Result<(), mysql::Error> {
+    conn.exec_drop(
+        r"CREATE TABLE IF NOT EXISTS user_roles (
+            bn_number VARCHAR(255) NOT NULL,
+            role_name VARCHAR(100) NOT NULL,
+            valid_from DATE,
+            valid_until DATE,
+            granted_by VARCHAR(255) NOT NULL,
+            granted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            PRIMARY KEY (bn_number, role_name),
+            FOREIGN KEY (bn_number) REFERENCES users(bn_number),
+            FOREIGN KEY (role_name) REFERENCES roles(role_name)
+        )",
+        ()
+    )
+}
+
+fn create_role_history_table_if_not_exists(conn: &mut PooledConn) -> Result<(), mysql::Error> {
+    conn.exec_drop(
+        r"CREATE TABLE IF NOT EXISTS role_history (
+            id INT AUTO_INCREMENT PRIMARY KEY,
+            bn_number VARCHAR(255) NOT NULL,
+            role_name VARCHAR(100) NOT NULL,
+            action VARCHAR(20) NOT NULL,
+            valid_from DATE,
+            valid_until DATE,
+            changed_by VARCHAR(255) NOT NULL,
+            changed_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+        )",
+        ()
+    )
+}
+
+// Users imported before role grants existed only carry users.role; give them a matching grant.
+fn backfill_user_roles(conn: &mut impl Queryable) -> Result<(), mysql::Error> {
+    conn.exec_drop(
+        r"INSERT IGNORE INTO user_roles (bn_number, role_name, granted_by)
+        SELECT users.bn_number, users.role, 'system' FROM users
+        JOIN roles ON roles.role_name = users.role",
+        ()
+    )
+}
+
+fn create_role_reconciliation_table_if_not_exists(conn: &mut PooledConn) -> Result<(), mysql::Error> {
+    conn.exec_drop(
+        r"CREATE TABLE IF NOT EXISTS role_reconciliation (
+            id INT AUTO_INCREMENT PRIMARY KEY,
+            bn_number VARCHAR(255) NOT NULL,
+            local_role VARCHAR(255) NOT NULL,
+            remote_role VARCHAR(255),
+            winner VARCHAR(10) NOT NULL,
+            decided_by VARCHAR(255) NOT NULL,
+            decided_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            FOREIGN KEY (bn_number) REFERENCES users(bn_number)
+        )",
+        ()
+    )
+}
+
+// Earlier versions stored a missing remote role as "", which never matched again.
+fn allow_null_remote_role(conn: &mut PooledConn) -> Result<(), mysql::Error> {
+    let nullable: Option<String> = conn.query_first(
+        r"SELECT IS_NULLABLE FROM information_schema.COLUMNS
+        WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = 'role_reconciliation' AND COLUMN_NAME = 'remote_role'",
+    )?;
+
+    if nullable.as_deref() != Some("YES") {
+        conn.query_drop("ALTER TABLE role_reconciliation MODIFY remote_role VARCHAR(255) NULL")?;
+        conn.query_drop("UPDATE role_reconciliation SET remote_role = NULL WHERE remote_role = ''")?;
+    }
+    Ok(())
+}
+
+fn create_sync_state_table_if_not_exists(conn: &mut PooledConn) -> Result<(), mysql::Error> {
+    conn.exec_drop(
+        r"CREATE TABLE IF NOT EXISTS sync_state (
+            dataset VARCHAR(50) PRIMARY KEY,
+            last_synced_at DATETIME NOT NULL
+        )",
+        ()
+    )
+}
+
+fn create_audit_log_table_if_not_exists(conn: &mut PooledConn) -> Result<(), mysql::Error> {
+    conn.exec_drop(
+        r"CREATE TABLE IF NOT EXISTS audit_log (
+            id INT AUTO_INCREMENT PRIMARY KEY,
+            actor VARCHAR(255) NOT NULL,
+            action VARCHAR(100) NOT NULL,
+            target VARCHAR(255) NOT NULL,
+            detail TEXT,
+            created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+        )",
+        ()
+    )
+}
+
+fn create_password_reset_table_if_not_exists(conn: &mut PooledConn) -> Result<(), mysql::Error> {
+    conn.exec_drop(
+        r"CREATE TABLE IF NOT EXISTS password_reset (
+            id INT AUTO_INCREMENT PRIMARY KEY,
+            bn_number VARCHAR(255) NOT NULL,
+            code_hash VARCHAR(255) NOT NULL,
+            issued_by VARCHAR(255) NOT NULL,
+            attempts INT UNSIGNED NOT NULL DEFAULT 0,
+            expires_at DATETIME NOT NULL,
+            used_at DATETIME,
+            FOREIGN KEY (bn_number) REFERENCES users(bn_number)
+        )",
+        ()
+    )
+}
+
 fn main() {
     let mysql_config = MySQLConfig::new(
         "root".to_string(),
@@ -689,8 +7055,11 @@ fn main() {
         "sr_exam".to_string(),
     );
 
+    env_logger::init();
+
     let mysql_url = mysql_config.format_url();
     let pool = Pool::new(&*mysql_url).expect("Failed to create MySQL pool");
+    let graphql_client = GraphQlClient::from_env();
 
     {
         let mut conn = pool.get_conn().expect("Failed to get MySQL connection");
@@ -698,23 +7067,82 @@ fn main() {
         create_shift_table_if_not_exists(&mut conn).expect("Failed to create shift table");
         create_room_table_if_not_exists(&mut conn).expect("Failed to create room table");
         create_enrollment_table_if_not_exists(&mut conn).expect("Failed to create enrollment table");
+        widen_enrollment_key_if_needed(&mut conn).expect("Failed to widen enrollment key");
         create_subject_table_if_not_exists(&mut conn).expect("Failed to create subject table");
         create_exam_transaction_if_not_exists(&mut conn).expect("Failed to create exam transaction");
+        create_exam_period_table_if_not_exists(&mut conn).expect("Failed to create exam period table");
+        create_blackout_date_table_if_not_exists(&mut conn).expect("Failed to create blackout date table");
+        create_room_attribute_table_if_not_exists(&mut conn).expect("Failed to create room attribute table");
+        create_room_unavailability_table_if_not_exists(&mut conn).expect("Failed to create room unavailability table");
+        create_campus_travel_table_if_not_exists(&mut conn).expect("Failed to create campus travel table");
+        add_column_if_missing(&mut conn, "exam_transaction", "revision", "INT UNSIGNED NOT NULL DEFAULT 0").expect("Failed to add exam transaction revision");
+        add_column_if_missing(&mut conn, "exam_transaction", "class_code", "VARCHAR(255)").expect("Failed to add exam transaction class");
+        add_column_if_missing(&mut conn, "shift", "retired", "BOOLEAN NOT NULL DEFAULT FALSE").expect("Failed to add shift retired flag");
+        widen_shift_id_if_needed(&mut conn).expect("Failed to widen shift id");
+        seed_default_shifts(&mut conn).expect("Failed to seed default shifts");
+        add_column_if_missing(&mut conn, "exam_transaction", "period_id", "INT UNSIGNED").expect("Failed to add exam transaction period");
+        bind_exam_transactions_to_periods(&mut conn).expect("Failed to bind exam transactions to periods");
+        create_seat_assignment_table_if_not_exists(&mut conn).expect("Failed to create seat assignment table");
+        create_exam_reschedule_table_if_not_exists(&mut conn).expect("Failed to create exam reschedule table");
+        add_column_if_missing(&mut conn, "exam_transaction", "makeup_of", "VARCHAR(50)").expect("Failed to add exam transaction makeup link");
+        create_makeup_request_table_if_not_exists(&mut conn).expect("Failed to create makeup request table");
+        create_password_policy_table_if_not_exists(&mut conn).expect("Failed to create password policy table");
+        create_password_history_table_if_not_exists(&mut conn).expect("Failed to create password history table");
+        create_audit_log_table_if_not_exists(&mut conn).expect("Failed to create audit log table");
+        create_password_reset_table_if_not_exists(&mut conn).expect("Failed to create password reset table");
+        create_roles_table_if_not_exists(&mut conn).expect("Failed to create roles table");
+        create_user_roles_table_if_not_exists(&mut conn).expect("Failed to create user roles table");
+        create_role_history_table_if_not_exists(&mut conn).expect("Failed to create role history table");
+        create_role_reconciliation_table_if_not_exists(&mut conn).expect("Failed to create role reconciliation table");
+        allow_null_remote_role(&mut conn).expect("Failed to allow missing remote roles");
 
-        task::block_on(async {
-            insert_users(&mut conn).await.expect("Failed to insert users");
-            insert_room(&mut conn).await.expect("Failed to insert rooms");
-            insert_subject(&mut conn).await.expect("Failed to insert subject");
-            insert_enrollment(&mut conn).await.expect("Failed to insert enrollment");
-        });
+        create_sync_state_table_if_not_exists(&mut conn).expect("Failed to create sync state table");
+
+        backfill_user_roles(&mut conn).expect("Failed to backfill user roles");
     }
 
     tauri::Builder::default()
         .manage(AppState {
             user: Mutex::new(None),
             mysql_pool: pool.clone(),
+            // The remote is authoritative; the local hash only answers while the remote is unavailable.
+            authenticators: vec![
+                // A login should not wait out the retry policy used for data syncs.
+                Box::new(RemoteAuthenticator {
+                    client: graphql_client.clone()
+                        .with_timeout(Duration::from_secs(REMOTE_AUTH_TIMEOUT_SECS))
+                        .with_max_retries(0),
+                }),
+                Box::new(LocalAuthenticator),
+            ],
         })
         .manage(pool.clone())
+        .manage(graphql_client)
+        .manage(SyncCache::from_env())
+        .setup(|app| {
+            // Master data is served from MySQL; pull anything stale from the remote without blocking startup.
+            let handle = app.handle();
+            tauri::async_runtime::spawn(async move {
+                for dataset in Dataset::ALL {
+                    let stale = match handle.state::<Pool>().get_conn() {
+                        Ok(mut conn) => handle
+                            .state::<SyncCache>()
+                            .ttl_minutes
+                            .lock()
+                            .map_err(|e| format!("Failed to lock mutex: {}", e))
+                            .map(|ttl_minutes| *ttl_minutes)
+                            .and_then(|ttl_minutes| cache_status(&mut conn, dataset, ttl_minutes))
+                            .map(|(_, stale)| stale)
+                            .unwrap_or(true),
+                        Err(_) => true,
+                    };
+                    if stale {
+                        revalidate(handle.clone(), dataset).await;
+                    }
+                }
+            });
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             login,
             get_current_user,
@@ -723,14 +7151,381 @@ fn main() {
             get_all_room,
             get_all_enrollment,
             get_all_shifts,
-            get_password_by_nim,
+            create_shift,
+            update_shift,
+            retire_shift,
+            get_exam_periods,
+            create_exam_period,
+            update_exam_period,
+            set_exam_period_locked,
+            archive_exam_period,
+            get_blackout_dates,
+            add_blackout_date,
+            remove_blackout_date,
+            import_blackout_ics,
+            get_room_details,
+            update_room_attributes,
+            get_room_unavailability,
+            add_room_unavailability,
+            remove_room_unavailability,
+            get_room_utilization,
+            get_campus_travel_times,
+            set_campus_travel_time,
+            get_available_rooms,
+            get_cache_ttl,
+            set_cache_ttl,
+            refresh_cache,
+            get_user_by_nim,
+            get_user_by_initial,
+            get_class_codes_by_subject_code,
+            get_students_by_class_and_subject_code,
+            get_enrollment_by_nim,
+            get_room_by_number,
+            get_room_by_campus,
+            get_room_by_capacity,
+            get_subject_by_code,
+            get_subject_by_name,
             change_password,
+            get_password_policy,
+            update_password_policy,
+            issue_password_reset,
+            redeem_password_reset,
             edit_role,
+            get_roles,
+            get_user_roles,
+            get_role_history,
+            grant_role,
+            revoke_role,
+            get_role_differences,
+            resolve_role_difference,
             update_exam_transaction,
             get_room_transaction,
             get_exam_transaction,
+            list_users,
+            list_rooms,
+            list_subjects,
+            list_exam_transactions,
+            search,
+            get_my_exam_schedule,
+            allocate_seats,
+            reschedule_exam_transaction,
+            get_reschedule_history,
+            request_makeup_exam,
+            cancel_exam_transaction,
+            get_makeup_requests,
+            review_makeup_request,
+            schedule_makeup_exam,
+            get_my_duty_roster,
+            export_timetable_ics,
+            export_student_ics,
+            export_proctor_ics,
+            export_exam_report,
+            export_exam_sheets,
+            export_exam_day_sheets,
+            import_exam_schedule,
+            import_master_data,
             insert_exam_transaction
         ])
         .run(tauri::generate_context!())
         .expect("Error while running Tauri application");
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn student(nim: &str, name: &str, initial: Option<&str>) -> User {
+        User {
+            bn_number: cynic::Id::new("BN001"),
+            nim: nim.to_string(),
+            name: name.to_string(),
+            major: "Computer Science".to_string(),
+            role: "Student".to_string(),
+            initial: initial.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn verify_password_accepts_both_schemes() {
+        let argon = hash_password("Secret123").unwrap();
+        assert!(verify_password("Secret123", &argon).unwrap());
+        assert!(!verify_password("secret123", &argon).unwrap());
+        assert!(!needs_rehash(&argon));
+
+        let legacy = hash("Secret123", 4).unwrap();
+        assert!(verify_password("Secret123", &legacy).unwrap());
+        assert!(needs_rehash(&legacy));
+    }
+
+    #[test]
+    fn verify_password_rejects_unknown_scheme() {
+        assert!(verify_password("Secret123", "Secret123").is_err());
+        assert!(needs_rehash("Secret123"));
+    }
+
+    #[test]
+    fn constant_time_eq_compares_whole_credentials() {
+        assert!(constant_time_eq(b"Secret123", b"Secret123"));
+        assert!(!constant_time_eq(b"Secret123", b"Secret124"));
+        assert!(!constant_time_eq(b"Secret123", b"Secret1234"));
+        assert!(!constant_time_eq(b"", b"Secret123"));
+        assert!(constant_time_eq(b"", b""));
+    }
+
+    #[test]
+    fn previous_password_match_skips_unknown_schemes() {
+        let history = vec!["Secret123".to_string(), hash_password("Other456").unwrap()];
+        assert!(!matches_previous_password("Secret123", &history).unwrap());
+        assert!(matches_previous_password("Other456", &history).unwrap());
+        assert!(!matches_previous_password("Secret123", &[]).unwrap());
+    }
+
+    #[test]
+    fn validate_password_reports_every_violation() {
+        let policy = PasswordPolicy::default();
+        let user = student("2401234567", "Budi Santoso", Some("BS24-1"));
+
+        assert!(validate_password(&policy, "Kuda1234", &user).is_ok());
+
+        let error = validate_password(&policy, "budi", &user).unwrap_err();
+        assert!(error.contains("at least 8 characters"), "{}", error);
+        assert!(error.contains("an uppercase letter"), "{}", error);
+        assert!(error.contains("a digit"), "{}", error);
+        assert!(error.contains("must not contain your NIM, initial or name"), "{}", error);
+    }
+
+    #[test]
+    fn validate_password_ignores_short_name_parts() {
+        let policy = PasswordPolicy::default();
+        let user = student("2401234567", "Li Wei", None);
+
+        assert!(validate_password(&policy, "Lighthouse9", &user).is_ok());
+        assert!(validate_password(&policy, "Xx2401234567", &user).is_err());
+    }
+
+    fn record(row: usize, fields: &[(&str, &str)]) -> TabularRecord {
+        TabularRecord {
+            row,
+            fields: fields.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect(),
+        }
+    }
+
+    fn schedule_lookups() -> ScheduleLookups {
+        ScheduleLookups {
+            subjects: HashSet::from(["COMP6047".to_string()]),
+            rooms: HashMap::from([("701".to_string(), "Anggrek".to_string())]),
+            shifts: HashMap::from([("1".to_string(), ("07:20:00".to_string(), "09:00:00".to_string()))]),
+            classes: HashMap::from([("LA01".to_string(), "COMP6047".to_string())]),
+            assistants: HashMap::from([
+                ("BN100".to_string(), "BN100".to_string()),
+                ("AB24-1".to_string(), "BN100".to_string()),
+            ]),
+            booked_rooms: HashSet::new(),
+            booked_proctors: HashSet::new(),
+            transaction_ids: HashSet::new(),
+            periods: vec![OpenPeriod {
+                period_id: 7,
+                start_date: "2026-11-01".to_string(),
+                end_date: "2026-11-30".to_string(),
+            }],
+            blackouts: Vec::new(),
+            unavailability: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn read_tabular_file_keys_csv_rows_by_normalized_header() {
+        let path = std::env::temp_dir().join(format!("sr-exam-import-{}.csv", std::process::id()));
+        std::fs::write(&path, "Subject Code,Room,Date,Shift-Id\nCOMP6047,701,2026-11-02,1\n,,,\nCOMP6047,702\n").unwrap();
+        let records = read_tabular_file(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        let records = records.unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].row, 2);
+        assert_eq!(records[0].get(&["subject_code"]).as_deref(), Some("COMP6047"));
+        assert_eq!(records[0].get(&["shift", "shift_id"]).as_deref(), Some("1"));
+        assert_eq!(records[1].row, 4);
+        assert_eq!(records[1].get(&["date", "transaction_date"]), None);
+    }
+
+    #[test]
+    fn validate_schedule_row_resolves_period_and_proctor_initial() {
+        let mut lookups = schedule_lookups();
+        let row = validate_schedule_row(
+            &record(2, &[("subject", "COMP6047"), ("class", "LA01"), ("room", "701"), ("date", "2026-11-02"), ("shift", "1"), ("proctor", "AB24-1")]),
+            None,
+            &mut lookups,
+        ).unwrap();
+
+        assert_eq!(row.period_id, 7);
+        assert_eq!(row.proctor.as_deref(), Some("BN100"));
+        assert_eq!(row.class_code.as_deref(), Some("LA01"));
+    }
+
+    #[test]
+    fn validate_schedule_row_reports_every_problem() {
+        let mut lookups = schedule_lookups();
+        let problems = validate_schedule_row(
+            &record(3, &[("subject", "MATH6001"), ("room", "999"), ("date", "02/11/2026"), ("shift", "9"), ("proctor", "XY")]),
+            None,
+            &mut lookups,
+        ).unwrap_err();
+
+        assert_eq!(problems, vec![
+            "Unknown subject MATH6001".to_string(),
+            "Unknown room 999".to_string(),
+            "Unknown shift 9".to_string(),
+            "Invalid date '02/11/2026', expected YYYY-MM-DD".to_string(),
+            "Proctor XY is not an active assistant".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn validate_schedule_row_rejects_closed_days_and_slots_taken_earlier_in_the_file() {
+        let mut lookups = schedule_lookups();
+        let sunday = record(2, &[("subject", "COMP6047"), ("room", "701"), ("date", "2026-11-01"), ("shift", "1")]);
+        let problems = validate_schedule_row(&sunday, None, &mut lookups).unwrap_err();
+        assert_eq!(problems, vec!["2026-11-01 is closed for exams: no exams on Sunday".to_string()]);
+
+        let monday = record(3, &[("subject", "COMP6047"), ("room", "701"), ("date", "2026-11-02"), ("shift", "1"), ("proctor", "BN100")]);
+        assert!(validate_schedule_row(&monday, None, &mut lookups).is_ok());
+
+        let clash = record(4, &[("subject", "COMP6047"), ("room", "701"), ("date", "2026-11-02"), ("shift", "1"), ("proctor", "AB24-1")]);
+        let problems = validate_schedule_row(&clash, None, &mut lookups).unwrap_err();
+        assert_eq!(problems, vec![
+            "Room 701 is already booked on 2026-11-02 shift 1".to_string(),
+            "Proctor BN100 is already assigned on 2026-11-02 shift 1".to_string(),
+        ]);
+    }
+
+    fn open_period(period_id: u32, start_date: &str, end_date: &str) -> OpenPeriod {
+        OpenPeriod {
+            period_id,
+            start_date: start_date.to_string(),
+            end_date: end_date.to_string(),
+        }
+    }
+
+    #[test]
+    fn pick_exam_period_uses_the_single_covering_period() {
+        let periods = vec![open_period(1, "2026-10-01", "2026-10-15"), open_period(2, "2026-11-01", "2026-11-30")];
+
+        assert_eq!(pick_exam_period(&periods, None, "2026-11-30"), Ok(2));
+        assert_eq!(pick_exam_period(&periods, Some(1), "2026-10-01"), Ok(1));
+        assert_eq!(
+            pick_exam_period(&periods, None, "2026-10-20"),
+            Err("No open exam period covers 2026-10-20".to_string())
+        );
+        assert_eq!(
+            pick_exam_period(&periods, Some(1), "2026-11-02"),
+            Err("2026-11-02 is outside exam period 1 (2026-10-01 to 2026-10-15)".to_string())
+        );
+    }
+
+    #[test]
+    fn pick_exam_period_requires_a_choice_between_overlapping_periods() {
+        let periods = vec![open_period(1, "2026-11-01", "2026-11-30"), open_period(2, "2026-11-20", "2026-12-10")];
+
+        assert_eq!(
+            pick_exam_period(&periods, None, "2026-11-25"),
+            Err("Several exam periods cover 2026-11-25; choose one".to_string())
+        );
+        assert_eq!(pick_exam_period(&periods, Some(2), "2026-11-25"), Ok(2));
+    }
+
+    #[test]
+    fn pick_exam_period_rejects_locked_periods() {
+        // Locked periods are left out of the open periods, so they are never picked or accepted.
+        let periods = vec![open_period(2, "2026-11-01", "2026-11-30")];
+
+        assert_eq!(
+            pick_exam_period(&periods, Some(1), "2026-11-02"),
+            Err("Exam period 1 does not exist or is locked".to_string())
+        );
+    }
+
+    fn date(value: &str) -> chrono::NaiveDate {
+        chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn parse_ics_closures_treats_all_day_dtend_as_exclusive() {
+        let contents = "BEGIN:VCALENDAR\nBEGIN:VEVENT\nDTSTART;VALUE=DATE:20261224\nDTEND;VALUE=DATE:20261226\nSUMMARY:Christmas\nEND:VEVENT\n\
+            BEGIN:VEVENT\nDTSTART;VALUE=DATE:20261231\nSUMMARY:New Year's Eve\nEND:VEVENT\nEND:VCALENDAR\n";
+
+        assert_eq!(parse_ics_closures(contents), vec![
+            (date("2026-12-24"), "Christmas".to_string()),
+            (date("2026-12-25"), "Christmas".to_string()),
+            (date("2026-12-31"), "New Year's Eve".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn parse_ics_closures_includes_the_end_date_of_timed_events() {
+        let contents = "BEGIN:VEVENT\nDTSTART:20261231T080000Z\nDTEND:20270101T120000Z\nSUMMARY:Network maintenance\nEND:VEVENT\n";
+
+        assert_eq!(parse_ics_closures(contents), vec![
+            (date("2026-12-31"), "Network maintenance".to_string()),
+            (date("2027-01-01"), "Network maintenance".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn parse_ics_closures_unfolds_continuation_lines() {
+        let contents = "BEGIN:VEVENT\r\nDTSTART;VALUE=DATE:20261117\r\nSUMMARY:Campus closed for the Dies Natalis\\, \r\n reopening the\r\n\tnext day\r\nEND:VEVENT\r\n";
+
+        assert_eq!(parse_ics_closures(contents), vec![
+            (date("2026-11-17"), "Campus closed for the Dies Natalis, reopening thenext day".to_string()),
+        ]);
+    }
+
+    fn proctor_slot(proctor: &str, exam: &str, start_time: &str, end_time: &str, campus: &str) -> ProctorSlot {
+        ProctorSlot {
+            proctor: proctor.to_string(),
+            exam: exam.to_string(),
+            date: "2026-11-02".to_string(),
+            start_time: start_time.to_string(),
+            end_time: end_time.to_string(),
+            campus: campus.to_string(),
+        }
+    }
+
+    #[test]
+    fn travel_warnings_ignore_back_to_back_duties_on_the_same_campus() {
+        let slots = vec![
+            proctor_slot("BN100", "TI0002", "09:20:00", "11:00:00", "Anggrek"),
+            proctor_slot("BN100", "TI0001", "07:20:00", "09:00:00", "Anggrek"),
+            proctor_slot("BN200", "TI0003", "09:20:00", "11:00:00", "Alam Sutera"),
+        ];
+
+        assert!(travel_warnings(slots, &HashMap::new()).is_empty());
+    }
+
+    #[test]
+    fn travel_warnings_flag_a_campus_switch_shorter_than_the_trip() {
+        let travel_times = HashMap::from([(campus_pair("Kemanggisan", "Alam Sutera"), 90)]);
+        let slots = vec![
+            proctor_slot("BN100", "TI0001", "07:20:00", "09:00:00", "Kemanggisan"),
+            proctor_slot("BN100", "TI0002", "09:20:00", "11:00:00", "Alam Sutera"),
+            proctor_slot("BN100", "TI0003", "13:00:00", "14:40:00", "Kemanggisan"),
+        ];
+
+        let warnings = travel_warnings(slots, &travel_times);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!((warnings[0].from_exam.as_str(), warnings[0].to_exam.as_str()), ("TI0001", "TI0002"));
+        assert_eq!((warnings[0].gap_minutes, warnings[0].required_minutes), (20, 90));
+    }
+
+    #[test]
+    fn travel_warnings_fall_back_to_the_default_trip_time() {
+        let slots = vec![
+            proctor_slot("BN100", "TI0001", "07:20:00", "09:00:00", "Anggrek"),
+            proctor_slot("BN100", "TI0002", "09:20:00", "11:00:00", "Bandung"),
+        ];
+
+        let warnings = travel_warnings(slots, &HashMap::new());
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].required_minutes, DEFAULT_CAMPUS_TRAVEL_MINUTES);
+    }
+}
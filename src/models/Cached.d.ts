@@ -0,0 +1,6 @@
+interface Cached<T> {
+    data: T[];
+    last_synced_at: string | null;
+    stale: boolean;
+    refreshing: boolean;
+}
@@ -2,4 +2,5 @@ interface Shift {
     shift_id: string;
     start_time: string;
     end_time: string;
+    retired: boolean;
 }
\ No newline at end of file
@@ -16,7 +16,7 @@ export default function HomePage() {
     const [users, setUsers] = useState<User[]>([]);
 
     useEffect(() => {
-        invoke<User[]>('get_all_users', {}).then((users) => {
+        invoke<Cached<User>>('get_all_users', {}).then(({ data: users }) => {
             setUsers(users as User[]);
         })
     }, []);
@@ -50,17 +50,17 @@ export default function ExamScheduler(): JSX.Element {
   useEffect(() => {
     const fetchData = async () => {
       try {
-        const enrollmentsResult: Enrollment[] = await invoke('get_all_enrollment');
+        const { data: enrollmentsResult }: Cached<Enrollment> = await invoke('get_all_enrollment');
         setEnrollments(enrollmentsResult);
 
-        const subjectsResult: Subject[] = await invoke('get_all_subject');
+        const { data: subjectsResult }: Cached<Subject> = await invoke('get_all_subject');
         const mappedSubjects = subjectsResult.map(subject => ({
           value: subject.subject_code_str,
           label: `${subject.subject_code_str} - ${subject.subject_name}`,
         }));
         setSubjects(mappedSubjects);
 
-        const roomsResult: Room[] = await invoke('get_all_room');
+        const { data: roomsResult }: Cached<Room> = await invoke('get_all_room');
         setRooms(roomsResult);
 
         const shiftsResult: Shift[] = await invoke('get_all_shifts');
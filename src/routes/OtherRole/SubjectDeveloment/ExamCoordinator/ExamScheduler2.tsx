@@ -49,7 +49,7 @@ const ExamScheduler2: React.FC = () => {
   useEffect(() => {
     const fetchAssistants = async () => {
       try {
-        const users: User[] = await invoke('get_all_users');
+        const { data: users }: Cached<User> = await invoke('get_all_users');
         const assistantsResult = users
           .filter(user => user.role === 'Assistant')
           .map(user => ({
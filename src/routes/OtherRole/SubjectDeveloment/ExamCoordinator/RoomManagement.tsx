@@ -33,7 +33,7 @@ export default function RoomManagement() {
   useEffect(() => {
     const fetchRooms = async () => {
       try {
-        const fetchedRooms = await invoke<Room[]>('get_all_room');
+        const { data: fetchedRooms } = await invoke<Cached<Room>>('get_all_room');
         console.log('Fetched Rooms:', fetchedRooms); 
         setRooms(fetchedRooms);
       } catch (error) {
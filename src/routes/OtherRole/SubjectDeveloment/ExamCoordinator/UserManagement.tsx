@@ -44,8 +44,8 @@ export default function UserManagement() {
   }, [searchQuery, users]);
 
   const fetchUsers = () => {
-    invoke<User[]>('get_all_users', {})
-      .then((users) => {
+    invoke<Cached<User>>('get_all_users', {})
+      .then(({ data: users }) => {
         console.log('Fetched users:', users); 
         setUsers(users);
       })
@@ -42,7 +42,7 @@ export default function ViewSchedule() {
             console.error('Failed to fetch current user:', error);
           });
 
-        invoke<User[]>('get_all_users', {}).then((users) => {
+        invoke<Cached<User>>('get_all_users', {}).then(({ data: users }) => {
             const assistants = users.filter(user => user.role === 'Assistant');
             const students = users.filter(user => user.role === 'Student');
             setAssistants(assistants);
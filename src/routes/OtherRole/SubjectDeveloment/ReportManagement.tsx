@@ -59,8 +59,8 @@ export default function ReportManagement() {
 
   useEffect(() => {
     invoke('get_all_users').then((response: any) => {
-      setUsers(response);
-      setCurrentUser(response[0]);
+      setUsers(response.data);
+      setCurrentUser(response.data[0]);
     });
     invoke('get_all_subjects').then((response: any) => setSubjects(response));
     invoke('get_all_rooms').then((response: any) => setRooms(response));
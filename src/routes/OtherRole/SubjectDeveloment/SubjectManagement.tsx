@@ -30,8 +30,8 @@ export default function SubjectManagement() {
 
     const fetchData = async () => {
       try {
-        const fetchedSubjects = await invoke<Subject[]>('get_all_subject');
-        setSubjects(fetchedSubjects);
+        const fetchedSubjects = await invoke<Cached<Subject>>('get_all_subject');
+        setSubjects(fetchedSubjects.data);
       } catch (error) {
         console.error('Error fetching subjects:', error);
       }